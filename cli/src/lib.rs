@@ -1,4 +1,8 @@
-use prettify::print;
+use prettify::{
+    from_json, print_doc_to_debug, print_source_with_config, print_with_config,
+    print_with_diagnostics, resolve_end_of_line, DiagnosedDoc, EndOfLine, LineWidthDiagnostic,
+    PrettifyConfig, PrettifyDoc, PRETTIFY_DEFAULT_CONFIG,
+};
 use prettify_json::format_json;
 use prettify_markdown::format_markdown;
 use prettify_toml::format_toml;
@@ -11,14 +15,28 @@ pub enum Language {
     Json,
 }
 
-pub fn format_by_language(contents: &str, language: Language) -> String {
-    let doc = match language {
+fn create_doc_by_language(contents: &str, language: Language) -> Result<PrettifyDoc, &str> {
+    match language {
         Language::Markdown => format_markdown(contents),
         Language::Toml => format_toml(contents),
         Language::Json => format_json(contents),
-    };
-    match doc {
-        Ok(doc) => print(doc),
+    }
+}
+
+// Files keep the line ending they already use the most.
+const CLI_CONFIG: PrettifyConfig = PrettifyConfig {
+    end_of_line: EndOfLine::Auto,
+    ..PRETTIFY_DEFAULT_CONFIG
+};
+
+// The config a file with `contents` is formatted and checked with.
+pub fn get_config(contents: &str) -> PrettifyConfig {
+    resolve_end_of_line(&CLI_CONFIG, contents)
+}
+
+pub fn format_by_language(contents: &str, language: Language) -> String {
+    match create_doc_by_language(contents, language) {
+        Ok(doc) => print_source_with_config(doc, contents, &get_config(contents)),
         Err(_) => {
             if cfg!(test) {
                 panic!("all test files should be parsed correctly.")
@@ -29,6 +47,42 @@ pub fn format_by_language(contents: &str, language: Language) -> String {
     }
 }
 
+// Formats `contents` with `config` without writing it and reports the lines that are still
+// wider than the print width, for `--check`.
+pub fn check_by_language<'a>(
+    contents: &'a str,
+    language: Language,
+    config: &PrettifyConfig,
+) -> Result<DiagnosedDoc, &'a str> {
+    create_doc_by_language(contents, language).map(|doc| print_with_diagnostics(doc, config))
+}
+
+// `config` is the config the line was checked with.
+pub fn get_line_width_warning(
+    path: &str,
+    diagnostic: &LineWidthDiagnostic,
+    config: &PrettifyConfig,
+) -> String {
+    format!(
+        "Warning: {}:{} is {} columns wide, more than the print width of {}, because of {:?}",
+        path,
+        diagnostic.line + 1,
+        diagnostic.width,
+        config.print_width,
+        diagnostic.string
+    )
+}
+
+// Renders the doc created for `contents` as builder calls to help debug a formatter.
+pub fn debug_doc_by_language(contents: &str, language: Language) -> Result<String, &str> {
+    create_doc_by_language(contents, language).map(print_doc_to_debug)
+}
+
+// Prints a doc that was saved as JSON, e.g., by `prettify::to_json` or by another tool.
+pub fn print_json_doc(contents: &str) -> Result<String, String> {
+    from_json(contents).map(|doc| print_with_config(doc, &PRETTIFY_DEFAULT_CONFIG))
+}
+
 pub fn get_language_from_filename(filename: &str) -> Option<Language> {
     if filename.ends_with(".md") {
         Some(Language::Markdown)
@@ -89,4 +143,84 @@ mod test {
         assert_eq!(get_elapsed_string(Duration::from_millis(1111)), "1 second");
         assert_eq!(get_elapsed_string(Duration::from_millis(2000)), "2 seconds");
     }
+
+    #[test]
+    fn debug_doc_by_language_test() {
+        assert_eq!(
+            debug_doc_by_language("[1]", Language::Json),
+            Ok(print_doc_to_debug(format_json("[1]").unwrap()))
+        );
+        assert!(debug_doc_by_language("[1]", Language::Json)
+            .unwrap()
+            .contains("string(\"1\")"));
+    }
+
+    #[test]
+    fn print_json_doc_test() {
+        let doc = format_json("[1,2]").unwrap();
+        assert_eq!(
+            print_json_doc(&prettify::to_json(&doc)),
+            Ok("[1, 2]\n".to_string())
+        );
+        assert!(print_json_doc("{}").is_err());
+        let nested = format!("{}{}", "[".repeat(200000), "]".repeat(200000));
+        assert!(print_json_doc(&nested).is_err());
+    }
+
+    #[test]
+    fn format_by_language_keeps_line_endings() {
+        assert_eq!(
+            format_by_language("{\r\n\"a\": [1,\r\n2]}\r\n", Language::Json),
+            "{\r\n    \"a\": [1, 2]\r\n}\r\n"
+        );
+        assert_eq!(
+            format_by_language("# Title\r\n\r\nsome\r\ntext\r\n", Language::Markdown),
+            "# Title\r\n\r\nsome text\r\n"
+        );
+        assert_eq!(
+            format_by_language("# Title\n\nsome\ntext\n", Language::Markdown),
+            "# Title\n\nsome text\n"
+        );
+    }
+
+    #[test]
+    fn check_by_language_test() {
+        let url = format!("https://example.com/{}", "a".repeat(80));
+        let markdown = format!("Some  text\n\nsee {}\n", url);
+        let config = get_config(&markdown);
+        let checked = check_by_language(&markdown, Language::Markdown, &config).unwrap();
+        assert_eq!(
+            checked.formatted,
+            format_by_language(&markdown, Language::Markdown)
+        );
+        assert_eq!(checked.diagnostics.len(), 1);
+        assert_eq!(checked.diagnostics[0].string, url);
+        assert_eq!(
+            get_line_width_warning("./README.md", &checked.diagnostics[0], &config),
+            format!(
+                "Warning: ./README.md:4 is 100 columns wide, more than the print width of 80, because of {:?}",
+                url
+            )
+        );
+        assert_eq!(
+            check_by_language("[1, 2]\n", Language::Json, &get_config("[1, 2]\n"))
+                .unwrap()
+                .diagnostics,
+            vec![]
+        );
+    }
+
+    #[test]
+    fn line_width_warning_uses_checked_width() {
+        let config = PrettifyConfig {
+            print_width: 10,
+            ..get_config("")
+        };
+        let checked = check_by_language("[\"a long string\"]", Language::Json, &config).unwrap();
+        assert_eq!(checked.diagnostics.len(), 1);
+        assert_eq!(
+            get_line_width_warning("./a.json", &checked.diagnostics[0], &config),
+            "Warning: ./a.json:2 is 19 columns wide, more than the print width of 10, because of \"\\\"a long string\\\"\""
+        );
+    }
 }
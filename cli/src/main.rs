@@ -2,16 +2,26 @@ extern crate glob;
 extern crate prettify;
 extern crate prettify_markdown;
 
-mod lib;
-
 use glob::glob;
-use lib::{format_by_language, get_elapsed_string, get_language_from_filename};
+use prettify_cli::{
+    check_by_language, debug_doc_by_language, format_by_language, get_config, get_elapsed_string,
+    get_language_from_filename, get_line_width_warning, print_json_doc,
+};
 use std::env;
 use std::time::Instant;
 
 fn main() {
     let start_time = Instant::now();
-    let args: Vec<String> = env::args().collect();
+    // `--debug-doc` prints the doc created for each file instead of formatting it.
+    let should_debug_doc = env::args().any(|arg| arg == "--debug-doc");
+    // `--print-json-doc` prints each file as a doc saved as JSON instead of formatting it.
+    let should_print_json_doc = env::args().any(|arg| arg == "--print-json-doc");
+    // `--check` reports files that aren't formatted and lines that are still too wide after
+    // formatting, without writing any files.
+    let should_check = env::args().any(|arg| arg == "--check");
+    let args: Vec<String> = env::args()
+        .filter(|arg| arg != "--debug-doc" && arg != "--print-json-doc" && arg != "--check")
+        .collect();
     let glob_pattern_index = if cfg!(debug_assertions) || args[1] == "prettify-cli" {
         2
     } else {
@@ -22,21 +32,66 @@ fn main() {
         Some(path) => path,
         None => default_glob_pattern,
     };
-    print!("\nFormatting files matching {}\n", glob_pattern);
+    print!(
+        "\n{} files matching {}\n",
+        if should_check {
+            "Checking"
+        } else {
+            "Formatting"
+        },
+        glob_pattern
+    );
     let files = match glob(glob_pattern) {
         Ok(files) => files,
         Err(e) => panic!("Error: {}", e),
     };
     let mut file_count = 0;
+    let mut unformatted_count = 0;
     for entry in files {
         match entry {
             Ok(path) => {
-                if path.is_file() {
+                if path.is_file() && should_print_json_doc {
+                    let contents = std::fs::read_to_string(&path).unwrap();
+                    match print_json_doc(&contents) {
+                        Ok(printed) => print!("\n./{}\n{}\n", path.display(), printed),
+                        Err(e) => print!("\n./{} is not a valid doc: {}\n", path.display(), e),
+                    }
+                } else if path.is_file() {
                     let language =
                         get_language_from_filename(path.file_name().unwrap().to_str().unwrap());
                     if let Some(matched_language) = language {
-                        let start_format_time = Instant::now();
                         let contents = std::fs::read_to_string(&path).unwrap();
+                        if should_debug_doc {
+                            match debug_doc_by_language(&contents, matched_language) {
+                                Ok(debug) => print!("\n./{}\n{}\n", path.display(), debug),
+                                Err(e) => {
+                                    print!("\n./{} could not be parsed: {}\n", path.display(), e)
+                                }
+                            }
+                            continue;
+                        }
+                        if should_check {
+                            let path = format!("./{}", path.display());
+                            let config = get_config(&contents);
+                            match check_by_language(&contents, matched_language, &config) {
+                                Ok(checked) => {
+                                    if checked.formatted != contents {
+                                        print!("\n{} is not formatted", path);
+                                        unformatted_count += 1;
+                                    }
+                                    for diagnostic in checked.diagnostics.iter() {
+                                        print!(
+                                            "\n{}",
+                                            get_line_width_warning(&path, diagnostic, &config)
+                                        );
+                                    }
+                                }
+                                Err(e) => print!("\n{} could not be parsed: {}", path, e),
+                            }
+                            file_count += 1;
+                            continue;
+                        }
+                        let start_format_time = Instant::now();
                         let formatted = format_by_language(&contents, matched_language);
                         std::fs::write(&path, formatted).unwrap();
                         let elapsed = start_format_time.elapsed();
@@ -54,6 +109,16 @@ fn main() {
     }
     let elapsed = start_time.elapsed();
     let elapsed_string = get_elapsed_string(elapsed);
+    if should_check {
+        println!(
+            "\n\nDone!\nChecked {} files in {}, {} not formatted",
+            file_count, elapsed_string, unformatted_count
+        );
+        if unformatted_count > 0 {
+            std::process::exit(1);
+        }
+        return;
+    }
     println!(
         "\n\nDone!\nFormatted {} files in {}",
         file_count, elapsed_string
@@ -1,11 +1,17 @@
 use crate::value::value;
 use prettify::PrettifyDoc;
-use prettify_shared::{repeated_items, RepeatedItemsOptions};
+use prettify_shared::{repeated_items, RepeatedItemsOptions, SourceContext};
 
-pub fn array(input: &str) -> nom::IResult<&str, PrettifyDoc> {
-    repeated_items(RepeatedItemsOptions::new("[", value, ",", "]").use_user_preferred_indentation())(
-        input,
-    )
+pub fn array<'a>(
+    input: &'a str,
+    source: Option<SourceContext<'a>>,
+) -> nom::IResult<&'a str, PrettifyDoc> {
+    repeated_items(
+        RepeatedItemsOptions::new("[", |input| value(input, source), ",", "]")
+            .use_user_preferred_indentation()
+            .break_nested_collections()
+            .with_source(source),
+    )(input)
 }
 
 #[cfg(test)]
@@ -16,35 +22,72 @@ mod test {
     #[test]
     fn test_array() {
         assert_formatted(
-            array("[\"hello\", \"world\"]"),
+            array("[\"hello\", \"world\"]", None),
             ("", "[\"hello\", \"world\"]"),
         );
         assert_formatted(
-            array("[\n\"hello\", \"world\"]"),
+            array("[\n\"hello\", \"world\"]", None),
             ("", "[\n    \"hello\",\n    \"world\"\n]"),
         );
-        assert_formatted(array("[\n123, 124]"), ("", "[\n    123,\n    124\n]"));
+        assert_formatted(array("[\n123, 124]", None), ("", "[\n    123,\n    124\n]"));
         assert_formatted(
-            array("[123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123]"),
+            array("[123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123, 123]", None),
             ("", "[\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123,\n    123\n]"),
         );
         assert_formatted(
-            array("[[\"hello\"], [\"world\"]]"),
+            array("[[\"hello\"], [\"world\"]]", None),
             ("", "[[\"hello\"], [\"world\"]]"),
         );
         assert_formatted(
-            array("[\n[\"hello\"], [\"world\"]]"),
+            array("[\n[\"hello\"], [\"world\"]]", None),
             ("", "[\n    [\"hello\"],\n    [\"world\"]\n]"),
         );
         assert_formatted(
-            array("[[\n\"hello\"], [\"world\"]]"),
+            array("[[\n\"hello\"], [\"world\"]]", None),
             (
                 "",
                 "[\n    [\n        \"hello\"\n    ],\n    [\"world\"]\n]",
             ),
         );
-        assert_formatted(array("['',]"), ("", "[\"\"]"));
-        assert_formatted(array("[null,]"), ("", "[null]"));
-        assert_formatted(array("[false,]"), ("", "[false]"));
+        assert_formatted(array("['',]", None), ("", "[\"\"]"));
+        assert_formatted(array("[null,]", None), ("", "[null]"));
+        assert_formatted(array("[false,]", None), ("", "[false]"));
+    }
+
+    #[test]
+    fn test_array_of_collections() {
+        assert_formatted(
+            array("[[1, 2], [3, 4]]", None),
+            ("", "[\n    [1, 2],\n    [3, 4]\n]"),
+        );
+        assert_formatted(
+            array("[{'a': 1, 'b': 2}, {'a': 3, 'b': 4}]", None),
+            (
+                "",
+                "[\n    { \"a\": 1, \"b\": 2 },\n    { \"a\": 3, \"b\": 4 }\n]",
+            ),
+        );
+        assert_formatted(
+            array("[[1, 2], {'a': 3, 'b': 4}]", None),
+            ("", "[[1, 2], { \"a\": 3, \"b\": 4 }]"),
+        );
+    }
+
+    #[test]
+    fn test_array_measures_visual_width() {
+        assert_formatted(
+            array(
+                "[\"ééééééééééééééééééééééééééééé\", \"ééééééééééééééééééééééééééééé\"]",
+                None,
+            ),
+            (
+                "",
+                "[\"ééééééééééééééééééééééééééééé\", \"ééééééééééééééééééééééééééééé\"]",
+            ),
+        );
+        assert_formatted(
+            array("[\"日本語日本語日本語日本語\", \"日本語日本語日本語日本語\", \"日本語日本語日本語日本語\"]", None),
+            ("", "[\n    \"日本語日本語日本語日本語\",\n    \"日本語日本語日本語日本語\",\n    \"日本語日本語日本語日本語\"\n]"),
+        );
     }
 }
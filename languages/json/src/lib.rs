@@ -1,9 +1,13 @@
 use nom::{
+    branch::alt,
     character::complete::multispace0,
-    combinator::{eof, map},
+    combinator::{all_consuming, eof, map},
     sequence::tuple,
 };
-use prettify::{concat, hard_line, PrettifyDoc};
+use object::key_value_pair;
+use prettify::{concat, hard_line, PrettifyConfig, PrettifyDoc};
+use prettify_shared::{find_enclosing_node, splice_formatted_node, SourceContext};
+use std::cell::RefCell;
 use value::value;
 
 mod array;
@@ -12,8 +16,30 @@ mod string;
 mod value;
 
 pub fn format_json(json: &str) -> Result<PrettifyDoc, &str> {
+    format(json, Some(SourceContext::new(json)))
+}
+
+// Formats `json` twice: once to find its tokens and once to place the cursor next to the
+// token it belongs to.
+pub fn format_json_with_cursor(json: &str, cursor_offset: usize) -> Result<PrettifyDoc, &str> {
+    let token_ranges = RefCell::new(vec![]);
+    format(
+        json,
+        Some(SourceContext::new(json).with_token_ranges(&token_ranges)),
+    )?;
+    let token_ranges = token_ranges.into_inner();
+    format(
+        json,
+        Some(SourceContext::new(json).with_cursor(cursor_offset, &token_ranges)),
+    )
+}
+
+fn format<'a>(
+    json: &'a str,
+    source: Option<SourceContext<'a>>,
+) -> Result<PrettifyDoc, &'static str> {
     match map(
-        tuple((multispace0, value, multispace0, eof)),
+        tuple((multispace0, |input| value(input, source), multispace0, eof)),
         |(_, content, _, _)| concat(vec![content, hard_line()]),
     )(json)
     {
@@ -21,3 +47,183 @@ pub fn format_json(json: &str) -> Result<PrettifyDoc, &str> {
         Err(_) => Err("Invalid json"),
     }
 }
+
+// Formats the smallest value or object member that contains `start..end` and leaves the rest
+// of `json` as it is. `json` is returned unchanged when the range is outside of its value.
+pub fn format_range<'a>(
+    json: &'a str,
+    start: usize,
+    end: usize,
+    config: &PrettifyConfig,
+) -> Result<String, &'a str> {
+    let node_ranges = RefCell::new(vec![]);
+    let context = SourceContext::new(json).with_node_ranges(&node_ranges);
+    if format(json, Some(context)).is_err() {
+        return Err("Invalid json");
+    }
+    let node = match find_enclosing_node(&node_ranges.borrow(), start, end) {
+        Some(node) => node,
+        None => return Ok(json.to_string()),
+    };
+    match alt((
+        all_consuming(|input| value(input, None)),
+        all_consuming(|input| key_value_pair(input, None)),
+    ))(&json[node.clone()])
+    {
+        Ok((_, doc)) => Ok(splice_formatted_node(json, node, doc, config)),
+        Err(_) => Err("Invalid json"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use prettify::{print_with_cursor, print_with_source_map, PRETTIFY_DEFAULT_CONFIG};
+
+    fn format_with_cursor(json: &str, cursor_offset: usize) -> (String, Vec<usize>) {
+        let printed = print_with_cursor(
+            format_json_with_cursor(json, cursor_offset).unwrap(),
+            &PRETTIFY_DEFAULT_CONFIG,
+        );
+        (
+            printed.formatted,
+            printed.cursors.iter().map(|cursor| cursor.byte).collect(),
+        )
+    }
+
+    #[test]
+    fn test_format_json_with_source_map() {
+        let json = "{'a':  [1,true]}";
+        let printed = print_with_source_map(format_json(json).unwrap(), &PRETTIFY_DEFAULT_CONFIG);
+        assert_eq!(printed.formatted, "{ \"a\": [1, true] }\n");
+        let tokens: Vec<(&str, &str)> = printed
+            .mappings
+            .iter()
+            .map(|mapping| {
+                (
+                    &printed.formatted[mapping.output.clone()],
+                    &json[mapping.input.clone()],
+                )
+            })
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                ("{", "{"),
+                ("\"a\"", "'a'"),
+                (":", ":"),
+                ("[", "["),
+                ("1", "1"),
+                (",", ","),
+                ("true", "true"),
+                ("]", "]"),
+                ("}", "}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_json_with_cursor() {
+        assert_eq!(
+            format_with_cursor("{'hello':   42}", 14),
+            ("{ \"hello\": 42 }\n".to_string(), vec![13])
+        );
+        assert_eq!(
+            format_with_cursor("{'hello':   42}", 10),
+            ("{ \"hello\": 42 }\n".to_string(), vec![11])
+        );
+        assert_eq!(
+            format_with_cursor("{'hello':   42}", 3),
+            ("{ \"hello\": 42 }\n".to_string(), vec![9])
+        );
+        assert_eq!(
+            format_with_cursor("[\n1, 2]", 5),
+            ("[\n    1,\n    2\n]\n".to_string(), vec![13])
+        );
+        assert_eq!(
+            format_with_cursor("  true  ", 0),
+            ("true\n".to_string(), vec![0])
+        );
+    }
+
+    #[test]
+    fn test_format_json_with_cursor_at_token_end() {
+        assert_eq!(
+            format_with_cursor("[1, 2]", 2),
+            ("[1, 2]\n".to_string(), vec![2])
+        );
+        assert_eq!(
+            format_with_cursor("{'hello':   42}", 8),
+            ("{ \"hello\": 42 }\n".to_string(), vec![9])
+        );
+    }
+
+    #[test]
+    fn test_format_json_with_cursor_on_delimiter() {
+        assert_eq!(
+            format_with_cursor("{'hello':   42}", 0),
+            ("{ \"hello\": 42 }\n".to_string(), vec![0])
+        );
+        assert_eq!(
+            format_with_cursor("{'hello':   42}", 9),
+            ("{ \"hello\": 42 }\n".to_string(), vec![10])
+        );
+        assert_eq!(
+            format_with_cursor("[1, 2]", 3),
+            ("[1, 2]\n".to_string(), vec![3])
+        );
+    }
+
+    #[test]
+    fn test_format_range() {
+        let json = "{\n    'a':  [1,true],\n    'b':{'c':   null,'d':2}\n}\n";
+        assert_eq!(
+            format_range(json, 14, 15, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "{\n    'a':  [1, true],\n    'b':{'c':   null,'d':2}\n}\n"
+        );
+        assert_eq!(
+            format_range(json, 6, 9, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "{\n    \"a\": [1, true],\n    'b':{'c':   null,'d':2}\n}\n"
+        );
+        assert_eq!(
+            format_range(json, 31, 46, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "{\n    'a':  [1,true],\n    'b':{ \"c\": null, \"d\": 2 }\n}\n"
+        );
+        assert_eq!(
+            format_range(json, 8, 30, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "{\n    \"a\": [1, true],\n    \"b\": { \"c\": null, \"d\": 2 }\n}\n"
+        );
+        assert_eq!(
+            format_range(" [1,2] ", 0, 0, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            " [1,2] "
+        );
+        assert!(format_range("{'a': }", 1, 2, &PRETTIFY_DEFAULT_CONFIG).is_err());
+    }
+
+    #[test]
+    fn test_format_range_keeps_indentation() {
+        let long = "1234567890".repeat(7);
+        let json = format!("{{\n  'a': {{\n    'b': ['{}',1]\n  }}\n}}\n", long);
+        assert_eq!(
+            format_range(&json, 21, 94, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            format!(
+                "{{\n  'a': {{\n    'b': [\n        \"{}\",\n        1\n    ]\n  }}\n}}\n",
+                long
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_range_uses_config() {
+        let json = "{\n\t'a':  [1,true],\n\t'b': 2\n}\n";
+        let config = PrettifyConfig {
+            print_width: 10,
+            use_tabs: true,
+            ..PRETTIFY_DEFAULT_CONFIG
+        };
+        assert_eq!(
+            format_range(json, 3, 6, &config).unwrap(),
+            "{\n\t\"a\": [\n\t\t1,\n\t\ttrue\n\t],\n\t'b': 2\n}\n"
+        );
+    }
+}
@@ -4,32 +4,63 @@ use nom::{
     bytes::complete::{is_not, tag},
     character::complete::multispace0,
     combinator::map,
-    sequence::{separated_pair, tuple},
+    sequence::tuple,
 };
 use prettify::{concat, string, PrettifyDoc};
-use prettify_shared::{repeated_items, RepeatedItemsOptions};
+use prettify_shared::{
+    mark_source_token, repeated_items, with_node, with_source, RepeatedItemsOptions, SourceContext,
+};
 
-pub fn object(input: &str) -> nom::IResult<&str, PrettifyDoc> {
+pub fn object<'a>(
+    input: &'a str,
+    source: Option<SourceContext<'a>>,
+) -> nom::IResult<&'a str, PrettifyDoc> {
     repeated_items(
-        RepeatedItemsOptions::new("{", key_value_pair, ",", "}")
+        RepeatedItemsOptions::new("{", |input| key_value_pair(input, source), ",", "}")
             .use_user_preferred_indentation()
-            .use_space_around_delimiters(),
+            .use_space_around_delimiters()
+            .with_source(source),
     )(input)
 }
 
-fn key(input: &str) -> nom::IResult<&str, PrettifyDoc> {
-    alt((
-        json_string,
-        map(is_not(":\n\r"), |result: &str| {
-            concat(vec![string("\""), string(result.trim()), string("\"")])
-        }),
-    ))(input)
+fn key<'a>(
+    input: &'a str,
+    source: Option<SourceContext<'a>>,
+) -> nom::IResult<&'a str, PrettifyDoc> {
+    with_source(
+        source,
+        alt((
+            json_string,
+            map(is_not(":\n\r"), |result: &str| {
+                concat(vec![string("\""), string(result.trim()), string("\"")])
+            }),
+        )),
+    )(input)
 }
 
-fn key_value_pair(input: &str) -> nom::IResult<&str, PrettifyDoc> {
-    map(
-        separated_pair(key, tuple((multispace0, tag(":"), multispace0)), value),
-        |(key, value)| concat(vec![key, string(": "), value]),
+pub fn key_value_pair<'a>(
+    input: &'a str,
+    source: Option<SourceContext<'a>>,
+) -> nom::IResult<&'a str, PrettifyDoc> {
+    with_node(
+        source,
+        map(
+            tuple((
+                |input| key(input, source),
+                multispace0,
+                tag(":"),
+                multispace0,
+                |input| value(input, source),
+            )),
+            |(key, _, colon, _, value)| {
+                concat(vec![
+                    key,
+                    mark_source_token(source, colon, string(":")),
+                    string(" "),
+                    value,
+                ])
+            },
+        ),
     )(input)
 }
 
@@ -41,57 +72,66 @@ mod test {
     #[test]
     fn test_object() {
         assert_formatted(
-            object("{\"hello\": \"world\"}"),
+            object("{\"hello\": \"world\"}", None),
             ("", "{ \"hello\": \"world\" }"),
         );
         assert_formatted(
-            object("{'hello': \"world\"}"),
+            object("{'hello': \"world\"}", None),
             ("", "{ \"hello\": \"world\" }"),
         );
         assert_formatted(
-            object("{hello: \"world\"}"),
+            object("{hello: \"world\"}", None),
             ("", "{ \"hello\": \"world\" }"),
         );
-        assert_formatted(object("{\"\": \"world\"}"), ("", "{ \"\": \"world\" }"));
-        assert_formatted(object("{'': \"world\"}"), ("", "{ \"\": \"world\" }"));
         assert_formatted(
-            object("{\n\"hello\"\n :\n\"world\"}"),
+            object("{\"\": \"world\"}", None),
+            ("", "{ \"\": \"world\" }"),
+        );
+        assert_formatted(object("{'': \"world\"}", None), ("", "{ \"\": \"world\" }"));
+        assert_formatted(
+            object("{\n\"hello\"\n :\n\"world\"}", None),
             ("", "{\n    \"hello\": \"world\"\n}"),
         );
         assert_formatted(
-            object("{  hello world  : 42}"),
+            object("{  hello world  : 42}", None),
             ("", "{ \"hello world\": 42 }"),
         );
         assert_formatted(
-            object("{\"this is a long key\": 1234,\"this is a long key\": 1234,\"this is a long key\": 1234}"),
+            object("{\"this is a long key\": 1234,\"this is a long key\": 1234,\"this is a long key\": 1234}", None),
             ("", "{\n    \"this is a long key\": 1234,\n    \"this is a long key\": 1234,\n    \"this is a long key\": 1234\n}"),
         );
         assert_formatted(
-            object("{\"hello\": {\"world\": 42}}"),
+            object("{\"hello\": {\"world\": 42}}", None),
             ("", "{ \"hello\": { \"world\": 42 } }"),
         );
         assert_formatted(
-            object("{\"hello 1\": [\"world\", 42], \"hello 2\": {\"world\": 42}}"),
+            object(
+                "{\"hello 1\": [\"world\", 42], \"hello 2\": {\"world\": 42}}",
+                None,
+            ),
             (
                 "",
                 "{ \"hello 1\": [\"world\", 42], \"hello 2\": { \"world\": 42 } }",
             ),
         );
         assert_formatted(
-            object("{\n\"hello 1\": [\"world\", 42], \"hello 2\": {\"world\": 42}}"),
+            object(
+                "{\n\"hello 1\": [\"world\", 42], \"hello 2\": {\"world\": 42}}",
+                None,
+            ),
             (
                 "",
                 "{\n    \"hello 1\": [\"world\", 42],\n    \"hello 2\": { \"world\": 42 }\n}",
             ),
         );
         assert_formatted(
-            object("{\"hello 1\": [\"world\", 42], \"hello 2\": {\n\"world\": 42}}"),
+            object("{\"hello 1\": [\"world\", 42], \"hello 2\": {\n\"world\": 42}}", None),
             (
                 "",
                 "{\n    \"hello 1\": [\"world\", 42],\n    \"hello 2\": {\n        \"world\": 42\n    }\n}",
             ),
         );
 
-        assert_errors(object("{: \"world\"}"));
+        assert_errors(object("{: \"world\"}", None));
     }
 }
@@ -1,18 +1,31 @@
 use nom::{branch::alt, bytes::complete::tag, combinator::map};
 use prettify::{string, PrettifyDoc};
-use prettify_shared::{float, integer, FloatOptions, IntegerOptions};
+use prettify_shared::{
+    float, integer, with_node, with_source, FloatOptions, IntegerOptions, SourceContext,
+};
 
 use crate::{array::array, object::object, string::json_string};
 
-pub fn value(input: &str) -> nom::IResult<&str, PrettifyDoc> {
-    alt((
-        float(FloatOptions::new()),
-        integer(IntegerOptions::new()),
-        json_string,
-        array,
-        object,
-        literals,
-    ))(input)
+pub fn value<'a>(
+    input: &'a str,
+    source: Option<SourceContext<'a>>,
+) -> nom::IResult<&'a str, PrettifyDoc> {
+    with_node(
+        source,
+        alt((
+            with_source(
+                source,
+                alt((
+                    float(FloatOptions::new()),
+                    integer(IntegerOptions::new()),
+                    json_string,
+                    literals,
+                )),
+            ),
+            |input| array(input, source),
+            |input| object(input, source),
+        )),
+    )(input)
 }
 
 fn literals(input: &str) -> nom::IResult<&str, PrettifyDoc> {
@@ -1,4 +1,5 @@
 use prettify::{concat, hard_line, join, string, PrettifyDoc};
+use prettify_shared::{mark_source_text, SourceContext};
 
 fn get_backtick_count(content: &str) -> usize {
     let mut count = 0;
@@ -12,16 +13,28 @@ fn get_backtick_count(content: &str) -> usize {
     count
 }
 
-pub fn format_fenced_code_block<'a>(info: &'a str, code: Vec<&'a str>) -> PrettifyDoc<'a> {
+pub fn format_fenced_code_block<'a>(
+    info: &'a str,
+    code: Vec<&'a str>,
+    source: Option<SourceContext<'a>>,
+) -> PrettifyDoc {
     let mut backtick_count = 3;
     for line in code.iter() {
         backtick_count = std::cmp::max(backtick_count, get_backtick_count(line.trim()) + 1);
     }
     let backticks = "`".repeat(backtick_count);
     concat(vec![
-        concat(vec![string(backticks.clone()), string(info.trim())]),
+        concat(vec![
+            string(backticks.clone()),
+            mark_source_text(source, info.trim()),
+        ]),
         hard_line(),
-        join(code.into_iter().map(string).collect(), hard_line()),
+        join(
+            code.into_iter()
+                .map(|line| mark_source_text(source, line))
+                .collect(),
+            hard_line(),
+        ),
         hard_line(),
         string(backticks),
         hard_line(),
@@ -36,51 +49,62 @@ mod tests {
     #[test]
     fn paragraph() {
         assert_eq!(
-            print(format_fenced_code_block("", vec!["hello world"])),
+            print(format_fenced_code_block("", vec!["hello world"], None)),
             "```\nhello world\n```\n"
         );
         assert_eq!(
-            print(format_fenced_code_block("", vec!["hello world   "])),
+            print(format_fenced_code_block("", vec!["hello world   "], None)),
             "```\nhello world\n```\n"
         );
         assert_eq!(
-            print(format_fenced_code_block("", vec!["  hello world"])),
+            print(format_fenced_code_block("", vec!["  hello world"], None)),
             "```\n  hello world\n```\n"
         );
         assert_eq!(
-            print(format_fenced_code_block("", vec!["hello", "world"])),
+            print(format_fenced_code_block("", vec!["hello", "world"], None)),
             "```\nhello\nworld\n```\n"
         );
         assert_eq!(
             print(format_fenced_code_block(
                 "",
-                vec!["hello", "", "   ", "world"]
+                vec!["hello", "", "   ", "world"],
+                None
             )),
             "```\nhello\n\n\nworld\n```\n"
         );
         assert_eq!(
-            print(format_fenced_code_block("", vec!["hello  ", "world   "])),
+            print(format_fenced_code_block(
+                "",
+                vec!["hello  ", "world   "],
+                None
+            )),
             "```\nhello\nworld\n```\n"
         );
         assert_eq!(
-            print(format_fenced_code_block("", vec!["hello", "```", "world"])),
+            print(format_fenced_code_block(
+                "",
+                vec!["hello", "```", "world"],
+                None
+            )),
             "````\nhello\n```\nworld\n````\n"
         );
         assert_eq!(
             print(format_fenced_code_block(
                 "ruby",
-                vec!["hello", "```", "world"]
+                vec!["hello", "```", "world"],
+                None
             )),
             "````ruby\nhello\n```\nworld\n````\n"
         );
         assert_eq!(
-            print(format_fenced_code_block(" ruby   ", vec!["hello"])),
+            print(format_fenced_code_block(" ruby   ", vec!["hello"], None)),
             "```ruby\nhello\n```\n"
         );
         assert_eq!(
             print(format_fenced_code_block(
                 "ruby startline=3 $%@#$",
-                vec!["hello"]
+                vec!["hello"],
+                None
             )),
             "```ruby startline=3 $%@#$\nhello\n```\n"
         );
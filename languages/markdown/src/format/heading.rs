@@ -1,27 +1,38 @@
 use prettify::{
     concat, conditional_group, fill, hard_line, join, join_to_vector, line, string, PrettifyDoc,
 };
+use prettify_shared::{mark_source_text, SourceContext};
 
-fn atx_heading_marker(level: usize) -> PrettifyDoc<'static> {
+fn atx_heading_marker(level: usize) -> PrettifyDoc {
     let heading_marker = "#";
     let heading_marker = heading_marker.repeat(level) + " ";
     string(heading_marker)
 }
 
-pub fn format_atx_heading(level: usize, content: &str) -> PrettifyDoc {
+pub fn format_atx_heading<'a>(
+    level: usize,
+    content: &'a str,
+    source: Option<SourceContext<'a>>,
+) -> PrettifyDoc {
     concat(vec![
         atx_heading_marker(level),
-        string(content),
+        mark_source_text(source, content),
         hard_line(),
     ])
 }
 
-pub fn format_setext_heading(level: usize, content: &str) -> PrettifyDoc {
+pub fn format_setext_heading<'a>(
+    level: usize,
+    content: &'a str,
+    source: Option<SourceContext<'a>>,
+) -> PrettifyDoc {
     let heading_marker = if level == 1 { "=" } else { "-" };
     let heading_marker = heading_marker.repeat(12);
 
-    let content_words: Vec<PrettifyDoc> =
-        content.replace('\n', " ").split(' ').map(string).collect();
+    let content_words: Vec<PrettifyDoc> = content
+        .split([' ', '\n'])
+        .map(|word| mark_source_text(source, word.trim_end_matches('\r')))
+        .collect();
 
     concat(vec![
         conditional_group(
@@ -36,7 +47,7 @@ pub fn format_setext_heading(level: usize, content: &str) -> PrettifyDoc {
                     string(heading_marker),
                 ]),
             ],
-            "setext_heading",
+            None,
         ),
         hard_line(),
     ])
@@ -50,11 +61,11 @@ mod tests {
     #[test]
     fn format_atx_heading_test() {
         assert_eq!(
-            print(format_atx_heading(1, "hello world")),
+            print(format_atx_heading(1, "hello world", None)),
             "# hello world\n"
         );
         assert_eq!(
-            print(format_atx_heading(2, "hello world")),
+            print(format_atx_heading(2, "hello world", None)),
             "## hello world\n"
         );
     }
@@ -62,26 +73,26 @@ mod tests {
     #[test]
     fn format_setext_heading_test() {
         assert_eq!(
-            print(format_setext_heading(1, "hello world")),
+            print(format_setext_heading(1, "hello world", None)),
             "# hello world\n"
         );
         assert_eq!(
-            print(format_setext_heading(2, "hello world")),
+            print(format_setext_heading(2, "hello world", None)),
             "## hello world\n"
         );
         assert_eq!(
-            print(format_setext_heading(1, "hello\nworld")),
+            print(format_setext_heading(1, "hello\nworld", None)),
             "# hello world\n"
         );
         assert_eq!(
-            print(format_setext_heading(2, "hello\nworld")),
+            print(format_setext_heading(2, "hello\nworld", None)),
             "## hello world\n"
         );
-        assert_eq!(print(format_setext_heading(2, "hello")), "## hello\n");
+        assert_eq!(print(format_setext_heading(2, "hello", None)), "## hello\n");
         assert_eq!(
             print(format_setext_heading(
                 1,
-                "this is an incredibly long header that definitely cannot fit on one line so this will need to be rendered as a setext heading"
+                "this is an incredibly long header that definitely cannot fit on one line so this will need to be rendered as a setext heading", None
             )),
             "this is an incredibly long header that definitely cannot fit on one line so this\nwill need to be rendered as a setext heading\n============\n"
         );
@@ -1,4 +1,5 @@
 use prettify::{concat, hard_line, join, string, PrettifyDoc};
+use prettify_shared::{mark_source_text, SourceContext};
 
 fn get_backtick_count(content: &str) -> usize {
     let mut count = 0;
@@ -12,7 +13,10 @@ fn get_backtick_count(content: &str) -> usize {
     count
 }
 
-pub fn format_indented_code_block(code: Vec<&str>) -> PrettifyDoc {
+pub fn format_indented_code_block<'a>(
+    code: Vec<&'a str>,
+    source: Option<SourceContext<'a>>,
+) -> PrettifyDoc {
     let mut backtick_count = 3;
     for line in code.iter() {
         backtick_count = std::cmp::max(backtick_count, get_backtick_count(line.trim()) + 1);
@@ -21,7 +25,12 @@ pub fn format_indented_code_block(code: Vec<&str>) -> PrettifyDoc {
     concat(vec![
         string(backticks.clone()),
         hard_line(),
-        join(code.into_iter().map(string).collect(), hard_line()),
+        join(
+            code.into_iter()
+                .map(|line| mark_source_text(source, line))
+                .collect(),
+            hard_line(),
+        ),
         hard_line(),
         string(backticks),
         hard_line(),
@@ -36,33 +45,40 @@ mod tests {
     #[test]
     fn paragraph() {
         assert_eq!(
-            print(format_indented_code_block(vec!["hello world"])),
+            print(format_indented_code_block(vec!["hello world"], None)),
             "```\nhello world\n```\n"
         );
         assert_eq!(
-            print(format_indented_code_block(vec!["hello world   "])),
+            print(format_indented_code_block(vec!["hello world   "], None)),
             "```\nhello world\n```\n"
         );
         assert_eq!(
-            print(format_indented_code_block(vec!["  hello world"])),
+            print(format_indented_code_block(vec!["  hello world"], None)),
             "```\n  hello world\n```\n"
         );
         assert_eq!(
-            print(format_indented_code_block(vec!["hello", "world"])),
+            print(format_indented_code_block(vec!["hello", "world"], None)),
             "```\nhello\nworld\n```\n"
         );
         assert_eq!(
-            print(format_indented_code_block(vec![
-                "hello", "", "   ", "world"
-            ])),
+            print(format_indented_code_block(
+                vec!["hello", "", "   ", "world"],
+                None
+            )),
             "```\nhello\n\n\nworld\n```\n"
         );
         assert_eq!(
-            print(format_indented_code_block(vec!["hello  ", "world   "])),
+            print(format_indented_code_block(
+                vec!["hello  ", "world   "],
+                None
+            )),
             "```\nhello\nworld\n```\n"
         );
         assert_eq!(
-            print(format_indented_code_block(vec!["hello", "```", "world"])),
+            print(format_indented_code_block(
+                vec!["hello", "```", "world"],
+                None
+            )),
             "````\nhello\n```\nworld\n````\n"
         );
     }
@@ -2,31 +2,37 @@ use crate::nodes::LeafBlock;
 
 use super::nodes::Block;
 use prettify::{concat, group, hard_line, join, string, PrettifyDoc};
+use prettify_shared::SourceContext;
 
 mod fenced_code_block;
 mod heading;
 mod indented_code_block;
 mod paragraph;
 
-pub fn create_prettify_doc(nodes: Vec<Block>) -> PrettifyDoc {
+pub fn create_prettify_doc<'a>(
+    nodes: Vec<Block<'a>>,
+    source: Option<SourceContext<'a>>,
+) -> PrettifyDoc {
     group(join(
         nodes
             .into_iter()
             .filter(|node| !matches!(node, Block::Leaf(LeafBlock::BlankLine)))
             .map(|node| match node {
                 Block::Leaf(LeafBlock::AtxHeading(size, content)) => {
-                    heading::format_atx_heading(size, content)
+                    heading::format_atx_heading(size, content, source)
                 }
                 Block::Leaf(LeafBlock::ThematicBreak) => concat(vec![string("---"), hard_line()]),
-                Block::Leaf(LeafBlock::Paragraph(content)) => paragraph::format_paragraph(content),
+                Block::Leaf(LeafBlock::Paragraph(content)) => {
+                    paragraph::format_paragraph(content, source)
+                }
                 Block::Leaf(LeafBlock::SetextHeading(size, content)) => {
-                    heading::format_setext_heading(size, content)
+                    heading::format_setext_heading(size, content, source)
                 }
                 Block::Leaf(LeafBlock::IndentedCodeBlock(code)) => {
-                    indented_code_block::format_indented_code_block(code)
+                    indented_code_block::format_indented_code_block(code, source)
                 }
                 Block::Leaf(LeafBlock::FencedCodeBlock(info, code)) => {
-                    fenced_code_block::format_fenced_code_block(info, code)
+                    fenced_code_block::format_fenced_code_block(info, code, source)
                 }
                 Block::Leaf(LeafBlock::BlankLine) => {
                     panic!("Blank lines are not renderable and should be removed before this point")
@@ -1,13 +1,13 @@
-use prettify::{concat, fill, hard_line, join_to_vector, line, string, PrettifyDoc};
+use prettify::{concat, fill, hard_line, join_to_vector, line, PrettifyDoc};
+use prettify_shared::{mark_source_text, SourceContext};
 
-pub fn format_paragraph(content: &str) -> PrettifyDoc {
+pub fn format_paragraph<'a>(content: &'a str, source: Option<SourceContext<'a>>) -> PrettifyDoc {
     concat(vec![
         fill(join_to_vector(
             content
-                .replace('\n', " ")
-                .split(' ')
+                .split([' ', '\n', '\r'])
                 .filter(|line| !line.is_empty())
-                .map(string)
+                .map(|word| mark_source_text(source, word))
                 .collect(),
             line(),
         )),
@@ -22,8 +22,29 @@ mod tests {
 
     #[test]
     fn paragraph() {
-        assert_eq!(print(format_paragraph("hello world")), "hello world\n");
-        assert_eq!(print(format_paragraph("hello\nworld")), "hello world\n");
-        assert_eq!(print(format_paragraph("hello\n  world")), "hello world\n");
+        assert_eq!(
+            print(format_paragraph("hello world", None)),
+            "hello world\n"
+        );
+        assert_eq!(
+            print(format_paragraph("hello\nworld", None)),
+            "hello world\n"
+        );
+        assert_eq!(
+            print(format_paragraph("hello\n  world", None)),
+            "hello world\n"
+        );
+    }
+
+    #[test]
+    fn paragraph_wraps_at_visual_width() {
+        assert_eq!(
+            print(format_paragraph("Le café était très animé ce matin-là, et les élèves répétaient déjà leurs leçons préférées.", None)),
+            "Le café était très animé ce matin-là, et les élèves répétaient déjà leurs leçons\npréférées.\n"
+        );
+        assert_eq!(
+            print(format_paragraph("日本語の文章 日本語の文章 日本語の文章 日本語の文章 日本語の文章 日本語の文章 語 日本語", None)),
+            "日本語の文章 日本語の文章 日本語の文章 日本語の文章 日本語の文章 日本語の文章 語\n日本語\n"
+        );
     }
 }
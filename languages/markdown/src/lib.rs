@@ -6,11 +6,146 @@ mod nodes;
 mod parse;
 
 use format::create_prettify_doc;
-use parse::parse_markdown;
+use parse::{parse_block_ranges, parse_markdown};
+use prettify::PrettifyConfig;
+use prettify_shared::{find_overlapping_nodes, splice_formatted_node, SourceContext};
+use std::cell::RefCell;
 
 pub fn format_markdown(markdown: &str) -> Result<prettify::PrettifyDoc, &str> {
+    format(markdown, Some(SourceContext::new(markdown)))
+}
+
+// Formats `markdown` twice: once to find its tokens and once to place the cursor next to the
+// token it belongs to.
+pub fn format_markdown_with_cursor(
+    markdown: &str,
+    cursor_offset: usize,
+) -> Result<prettify::PrettifyDoc, &str> {
+    let token_ranges = RefCell::new(vec![]);
+    format(
+        markdown,
+        Some(SourceContext::new(markdown).with_token_ranges(&token_ranges)),
+    )?;
+    let token_ranges = token_ranges.into_inner();
+    format(
+        markdown,
+        Some(SourceContext::new(markdown).with_cursor(cursor_offset, &token_ranges)),
+    )
+}
+
+fn format<'a>(
+    markdown: &'a str,
+    source: Option<SourceContext<'a>>,
+) -> Result<prettify::PrettifyDoc, &'static str> {
     match parse_markdown(markdown) {
-        Ok((_, nodes)) => Ok(create_prettify_doc(nodes)),
+        Ok((_, nodes)) => Ok(create_prettify_doc(nodes, source)),
         Err(_) => Err("Invalid markdown"),
     }
 }
+
+// Formats every block that `start..end` touches and leaves the rest of `markdown` as it is.
+// `markdown` is returned unchanged when the range only touches blank lines.
+pub fn format_range<'a>(
+    markdown: &'a str,
+    start: usize,
+    end: usize,
+    config: &PrettifyConfig,
+) -> Result<String, &'a str> {
+    let blocks = match parse_block_ranges(markdown) {
+        Ok((_, blocks)) => blocks,
+        Err(_) => return Err("Invalid markdown"),
+    };
+    let node = match find_overlapping_nodes(&blocks, start, end) {
+        Some(node) => node,
+        None => return Ok(markdown.to_string()),
+    };
+    let doc = format(&markdown[node.clone()], Some(SourceContext::new(markdown)))?;
+    Ok(splice_formatted_node(markdown, node, doc, config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prettify::{print_with_cursor, print_with_source_map, PRETTIFY_DEFAULT_CONFIG};
+
+    fn format_with_cursor(markdown: &str, cursor_offset: usize) -> (String, Vec<usize>) {
+        let printed = print_with_cursor(
+            format_markdown_with_cursor(markdown, cursor_offset).unwrap(),
+            &PRETTIFY_DEFAULT_CONFIG,
+        );
+        (
+            printed.formatted,
+            printed.cursors.iter().map(|cursor| cursor.byte).collect(),
+        )
+    }
+
+    #[test]
+    fn format_markdown_with_source_map() {
+        let markdown = "# Hello   world\n\nsome  text\n";
+        let printed =
+            print_with_source_map(format_markdown(markdown).unwrap(), &PRETTIFY_DEFAULT_CONFIG);
+        assert_eq!(printed.formatted, "# Hello   world\n\nsome text\n");
+        let tokens: Vec<(&str, usize)> = printed
+            .mappings
+            .iter()
+            .map(|mapping| {
+                assert_eq!(
+                    &printed.formatted[mapping.output.clone()],
+                    &markdown[mapping.input.clone()]
+                );
+                (&markdown[mapping.input.clone()], mapping.output.start)
+            })
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![("Hello   world", 2), ("some", 17), ("text", 22)]
+        );
+    }
+
+    #[test]
+    fn format_markdown_with_cursor_test() {
+        let markdown = "# Hello   world\n\n\nsome  text\nhere\n";
+        let formatted = "# Hello   world\n\nsome text here\n".to_string();
+        assert_eq!(
+            format_with_cursor(markdown, 4),
+            (formatted.clone(), vec![4])
+        );
+        assert_eq!(
+            format_with_cursor(markdown, 23),
+            (formatted.clone(), vec![22])
+        );
+        assert_eq!(
+            format_with_cursor(markdown, 26),
+            (formatted.clone(), vec![24])
+        );
+        assert_eq!(
+            format_with_cursor(markdown, 29),
+            (formatted.clone(), vec![27])
+        );
+        assert_eq!(
+            format_with_cursor("```rust\nfn  main()\n```\n", 12),
+            ("```rust\nfn  main()\n```\n".to_string(), vec![12])
+        );
+    }
+
+    #[test]
+    fn format_range_test() {
+        let markdown = "first  para\n\n\nsecond  para\nhere\n\nthird  para\n";
+        assert_eq!(
+            format_range(markdown, 16, 18, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "first  para\n\n\nsecond para here\n\nthird  para\n"
+        );
+        assert_eq!(
+            format_range(markdown, 3, 16, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "first para\n\nsecond para here\n\nthird  para\n"
+        );
+        assert_eq!(
+            format_range(markdown, 13, 13, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            markdown
+        );
+        assert_eq!(
+            format_range("# Hello   world\n---\n", 18, 19, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "# Hello   world\n---\n"
+        );
+    }
+}
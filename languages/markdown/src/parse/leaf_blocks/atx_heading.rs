@@ -23,7 +23,7 @@ fn trim_content(content: &str) -> &str {
     content.trim_end_matches('#').trim_end()
 }
 
-pub fn atx_heading(input: &str) -> nom::IResult<&str, LeafBlock> {
+pub fn atx_heading(input: &str) -> nom::IResult<&str, LeafBlock<'_>> {
     let (remainder, (hashtags, content)) = delimited(
         many_m_n(0, 3, space),
         tuple((
@@ -2,7 +2,7 @@ use nom::{bytes::complete::is_a, combinator::opt, sequence::terminated};
 
 use crate::{nodes::LeafBlock, parse::preliminaries::line_ending};
 
-pub fn blank_line(input: &str) -> nom::IResult<&str, LeafBlock> {
+pub fn blank_line(input: &str) -> nom::IResult<&str, LeafBlock<'_>> {
     let (remainder, _) = terminated(opt(is_a(" \t")), line_ending)(input)?;
     Ok((remainder, LeafBlock::BlankLine))
 }
@@ -11,7 +11,7 @@ use crate::{
     parse::preliminaries::{any_until_line_ending, line, line_ending, space0, SPACE_STR},
 };
 
-pub fn fenced_code_block(input: &str) -> nom::IResult<&str, LeafBlock> {
+pub fn fenced_code_block(input: &str) -> nom::IResult<&str, LeafBlock<'_>> {
     let (remainder, (indent, fence)) = tuple((
         many_m_n(0, 3, tag(SPACE_STR)),
         alt((
@@ -23,7 +23,7 @@ fn empty_line(input: &str) -> nom::IResult<&str, &str> {
     terminated(is_a(" \t"), line_ending)(input)
 }
 
-pub fn indented_code_block(input: &str) -> nom::IResult<&str, LeafBlock> {
+pub fn indented_code_block(input: &str) -> nom::IResult<&str, LeafBlock<'_>> {
     let (remainder, (first_line, mut lines)) = tuple((
         code_block_line,
         many0(alt((
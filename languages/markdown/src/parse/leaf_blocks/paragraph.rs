@@ -13,7 +13,7 @@ use nom::{
 
 use super::fenced_code_block;
 
-pub fn paragraph(input: &str) -> nom::IResult<&str, LeafBlock> {
+pub fn paragraph(input: &str) -> nom::IResult<&str, LeafBlock<'_>> {
     let (remainder, content) = alt((
         recognize(many_till(
             tuple((any_until_line_ending, line_ending)),
@@ -20,7 +20,7 @@ fn heading_underline(input: &str) -> nom::IResult<&str, usize> {
     Ok((remainder, if underline.contains('=') { 1 } else { 2 }))
 }
 
-pub fn setext_heading(input: &str) -> nom::IResult<&str, LeafBlock> {
+pub fn setext_heading(input: &str) -> nom::IResult<&str, LeafBlock<'_>> {
     let result = tuple((
         recognize(many_till(
             delimited(
@@ -12,7 +12,7 @@ use crate::{
     parse::preliminaries::{line_ending, space, space0},
 };
 
-pub fn thematic_break(input: &str) -> nom::IResult<&str, LeafBlock> {
+pub fn thematic_break(input: &str) -> nom::IResult<&str, LeafBlock<'_>> {
     let (remainder, _) = tuple((
         many_m_n(0, 3, space),
         alt((
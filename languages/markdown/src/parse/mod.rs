@@ -2,13 +2,18 @@ use self::leaf_blocks::{
     atx_heading, blank_line, fenced_code_block, indented_code_block, paragraph, setext_heading,
     thematic_break,
 };
-use super::nodes::Block;
-use nom::{branch::alt, combinator::eof, multi::many_till};
+use super::nodes::{Block, LeafBlock};
+use nom::{
+    branch::alt,
+    combinator::{consumed, eof},
+    multi::many_till,
+};
+use std::ops::Range;
 
 mod leaf_blocks;
 mod preliminaries;
 
-fn leaf_block_as_block(input: &str) -> nom::IResult<&str, Block> {
+fn leaf_block_as_block(input: &str) -> nom::IResult<&str, Block<'_>> {
     let result = alt((
         blank_line,
         indented_code_block,
@@ -24,10 +29,32 @@ fn leaf_block_as_block(input: &str) -> nom::IResult<&str, Block> {
     }
 }
 
-pub fn parse_markdown(markdown: &str) -> nom::IResult<&str, Vec<Block>> {
+pub fn parse_markdown(markdown: &str) -> nom::IResult<&str, Vec<Block<'_>>> {
     let result = many_till(leaf_block_as_block, eof)(markdown);
     match result {
         Ok((remainder, (blocks, _))) => Ok((remainder, blocks)),
         Err(error) => Err(error),
     }
 }
+
+// Returns the range of each block that isn't blank, up to the end of its last line, so a block can be formatted on its own.
+pub fn parse_block_ranges(markdown: &str) -> nom::IResult<&str, Vec<Range<usize>>> {
+    let (remainder, (blocks, _)) = many_till(consumed(leaf_block_as_block), eof)(markdown)?;
+    Ok((
+        remainder,
+        blocks
+            .into_iter()
+            .filter(|(_, block)| !matches!(block, Block::Leaf(LeafBlock::BlankLine)))
+            .map(|(text, _)| {
+                // Blocks can consume the blank lines after them, which belong between blocks.
+                let content = text.trim_end();
+                let line_ending = ["\r\n", "\n"]
+                    .into_iter()
+                    .find(|line_ending| text[content.len()..].starts_with(line_ending))
+                    .map_or(0, str::len);
+                let start = text.as_ptr() as usize - markdown.as_ptr() as usize;
+                start..start + content.len() + line_ending
+            })
+            .collect(),
+    ))
+}
@@ -13,15 +13,9 @@ pub const CARRIAGE_RETURN_CHAR: char = '\r';
 pub const CARRIAGE_RETURN_STR: &str = "\r";
 pub const SPACE_CHAR: char = ' ';
 pub const SPACE_STR: &str = " ";
-pub const TAB_CHAR: char = '\t';
-pub const TAB_STR: &str = "\t";
-pub const LINE_TABULATION_CHAR: char = '\x0B';
-pub const LINE_TABULATION_STR: &str = "\x0B";
-pub const FORM_FEED_CHAR: char = '\x0C';
-pub const FORM_FEED_STR: &str = "\x0C";
 
 pub fn line_ending_no_eof(input: &str) -> nom::IResult<&str, &str> {
-    alt((tag(NEWLINE_STR), tag(CARRIAGE_RETURN_STR), tag("\r\n")))(input)
+    alt((tag("\r\n"), tag(NEWLINE_STR), tag(CARRIAGE_RETURN_STR)))(input)
 }
 
 pub fn line_ending(input: &str) -> nom::IResult<&str, &str> {
@@ -38,23 +32,6 @@ pub fn line(input: &str) -> nom::IResult<&str, &str> {
     )(input)
 }
 
-pub fn is_whitespace_char(char: char) -> bool {
-    char == SPACE_CHAR
-        || char == TAB_CHAR
-        || char == NEWLINE_CHAR
-        || char == CARRIAGE_RETURN_CHAR
-        || char == LINE_TABULATION_CHAR
-        || char == FORM_FEED_CHAR
-}
-
-pub fn whitespace0(input: &str) -> nom::IResult<&str, &str> {
-    take_while(is_whitespace_char)(input)
-}
-
-pub fn whitespace1(input: &str) -> nom::IResult<&str, &str> {
-    take_while1(is_whitespace_char)(input)
-}
-
 pub fn is_space(char: char) -> bool {
     char == SPACE_CHAR
 }
@@ -71,18 +48,6 @@ pub fn space1(input: &str) -> nom::IResult<&str, &str> {
     take_while1(is_space)(input)
 }
 
-pub fn is_inline_whitespace(char: char) -> bool {
-    char == SPACE_CHAR || char == TAB_CHAR
-}
-
-pub fn inline_whitespace0(input: &str) -> nom::IResult<&str, &str> {
-    take_while(is_inline_whitespace)(input)
-}
-
-pub fn inline_whitespace1(input: &str) -> nom::IResult<&str, &str> {
-    take_while1(is_inline_whitespace)(input)
-}
-
 pub fn any_until_line_ending(input: &str) -> nom::IResult<&str, &str> {
     recognize(many_till(anychar, peek(line_ending)))(input)
 }
@@ -110,6 +75,9 @@ mod test {
         assert_eq!(line_ending("\n\nhello"), Ok(("\nhello", "\n")));
         assert_eq!(line_ending("\n"), Ok(("", "\n")));
         assert_eq!(line_ending("\nhello"), Ok(("hello", "\n")));
+        assert_eq!(line_ending("\r\nhello"), Ok(("hello", "\r\n")));
+        assert_eq!(line_ending("\r\n\r\n"), Ok(("\r\n", "\r\n")));
+        assert_eq!(line_ending("\rhello"), Ok(("hello", "\r")));
 
         // only matches a 0 line breaks if it's the end of the file
         assert_eq!(line_ending(""), Ok(("", "")));
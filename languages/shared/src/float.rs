@@ -14,6 +14,12 @@ pub struct FloatOptions {
     use_underscores: bool,
 }
 
+impl Default for FloatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FloatOptions {
     pub fn new() -> Self {
         FloatOptions {
@@ -29,7 +35,7 @@ impl FloatOptions {
 
 fn parse_exponent<'a>(
     options: FloatOptions,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(
         preceded(tag_no_case("e"), tuple((opt_sign, is_a("0123456789_")))),
         move |(sign, mut digits)| {
@@ -54,7 +60,7 @@ fn parse_exponent<'a>(
 */
 pub fn float<'a>(
     options: FloatOptions,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(
         alt((
             tuple((
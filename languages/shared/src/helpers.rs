@@ -4,7 +4,7 @@ use prettify::{string, PrettifyDoc};
 pub fn trim_value(input: &str) -> &str {
     let value = input
         .trim_end_matches('_')
-        .trim_start_matches(|char| char == '_' || char == '0' || char == ' ');
+        .trim_start_matches(['_', '0', ' ']);
     if value.is_empty() {
         "0"
     } else {
@@ -19,6 +19,12 @@ pub struct IntegerOptions {
     parse_binary: bool,
 }
 
+impl Default for IntegerOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl IntegerOptions {
     pub fn new() -> Self {
         IntegerOptions {
@@ -71,7 +77,7 @@ impl IntegerOptions {
 */
 pub fn integer<'a>(
     options: IntegerOptions,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     alt((
         map_res(
             cond(options.parse_binary, binary_integer(options)),
@@ -106,7 +112,7 @@ pub fn integer<'a>(
 
 fn decimal_integer<'a>(
     options: IntegerOptions,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(
         tuple((opt_sign, is_a("0123456789_"))),
         move |(sign, mut value)| {
@@ -128,7 +134,7 @@ fn decimal_integer<'a>(
 
 fn hexadecimal_integer<'a>(
     options: IntegerOptions,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(
         tuple((
             opt_sign,
@@ -159,7 +165,7 @@ fn hexadecimal_integer<'a>(
 
 fn octal_integer<'a>(
     options: IntegerOptions,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(
         tuple((
             opt_sign,
@@ -183,7 +189,7 @@ fn octal_integer<'a>(
 
 fn binary_integer<'a>(
     options: IntegerOptions,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(
         tuple((
             opt_sign,
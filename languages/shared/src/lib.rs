@@ -1,7 +1,9 @@
 pub mod float;
 pub mod helpers;
 pub mod integer;
+pub mod range;
 pub mod repeated_items;
+pub mod source_context;
 pub mod string;
 pub mod test_helpers;
 pub mod time_rfc_3339;
@@ -9,7 +11,9 @@ pub mod time_rfc_3339;
 pub use float::*;
 pub use helpers::*;
 pub use integer::*;
+pub use range::*;
 pub use repeated_items::*;
+pub use source_context::*;
 pub use string::*;
 pub use test_helpers::*;
 pub use time_rfc_3339::*;
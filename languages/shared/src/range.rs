@@ -0,0 +1,145 @@
+use prettify::{
+    align, concat, print_source_with_config, string, AlignAmount, PrettifyConfig, PrettifyDoc,
+};
+use std::ops::Range;
+
+// Returns the smallest of `nodes` that contains all of `start..end`.
+pub fn find_enclosing_node(
+    nodes: &[Range<usize>],
+    start: usize,
+    end: usize,
+) -> Option<Range<usize>> {
+    nodes
+        .iter()
+        .filter(|node| node.start <= start && end <= node.end)
+        .min_by_key(|node| node.len())
+        .cloned()
+}
+
+// Returns the range from the first to the last of the sibling `nodes` that `start..end`
+// touches, e.g., every Markdown block that is part of a selection.
+pub fn find_overlapping_nodes(
+    nodes: &[Range<usize>],
+    start: usize,
+    end: usize,
+) -> Option<Range<usize>> {
+    let mut overlapping = nodes
+        .iter()
+        .filter(|node| node.start <= end && start < node.end);
+    let first = overlapping.next()?;
+    let last = overlapping.next_back().unwrap_or(first);
+    Some(first.start..last.end)
+}
+
+// Prints `doc`, the formatted version of the `node` range of `source`, and splices it into
+// the untouched text around it. The text before the node on its line is printed with it, so
+// the print width is measured from the node's column, and lines after the first are indented
+// like the line the node starts on. `EndOfLine::Auto` uses the line ending of `source`.
+pub fn splice_formatted_node(
+    source: &str,
+    node: Range<usize>,
+    doc: PrettifyDoc,
+    config: &PrettifyConfig,
+) -> String {
+    let line_start = source[..node.start]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    let prefix = &source[line_start..node.start];
+    let indentation = &prefix[..prefix.len() - prefix.trim_start().len()];
+    let printed = print_source_with_config(
+        concat(vec![
+            string(prefix),
+            align(doc, AlignAmount::String(indentation.to_string())),
+        ]),
+        source,
+        config,
+    );
+    let formatted = printed.strip_prefix(prefix).unwrap_or(&printed);
+    format!(
+        "{}{}{}",
+        &source[..node.start],
+        formatted,
+        &source[node.end..]
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use prettify::{group, indent, join, line, soft_line, EndOfLine, PRETTIFY_DEFAULT_CONFIG};
+
+    #[test]
+    fn find_enclosing_node_test() {
+        let nodes = vec![0..20, 2..10, 4..6, 12..18];
+        assert_eq!(find_enclosing_node(&nodes, 4, 5), Some(4..6));
+        assert_eq!(find_enclosing_node(&nodes, 4, 8), Some(2..10));
+        assert_eq!(find_enclosing_node(&nodes, 8, 14), Some(0..20));
+        assert_eq!(find_enclosing_node(&nodes, 18, 18), Some(12..18));
+        assert_eq!(find_enclosing_node(&nodes, 19, 22), None);
+    }
+
+    #[test]
+    fn find_overlapping_nodes_test() {
+        let nodes = vec![0..4, 6..10, 12..16];
+        assert_eq!(find_overlapping_nodes(&nodes, 1, 2), Some(0..4));
+        assert_eq!(find_overlapping_nodes(&nodes, 3, 7), Some(0..10));
+        assert_eq!(find_overlapping_nodes(&nodes, 5, 14), Some(6..16));
+        assert_eq!(find_overlapping_nodes(&nodes, 4, 6), Some(6..10));
+        assert_eq!(find_overlapping_nodes(&nodes, 17, 20), None);
+    }
+
+    #[test]
+    fn splice_formatted_node_test() {
+        let source = "before\n  key: [1,2]  \nafter";
+        let items = |items: Vec<&str>| {
+            group(concat(vec![
+                string("["),
+                indent(concat(vec![
+                    soft_line(),
+                    join(
+                        items.into_iter().map(string).collect(),
+                        concat(vec![string(","), line()]),
+                    ),
+                ])),
+                soft_line(),
+                string("]"),
+            ]))
+        };
+        assert_eq!(
+            splice_formatted_node(
+                source,
+                14..19,
+                items(vec!["1", "2"]),
+                &PRETTIFY_DEFAULT_CONFIG
+            ),
+            "before\n  key: [1, 2]  \nafter"
+        );
+        let long_item = "a".repeat(70);
+        assert_eq!(
+            splice_formatted_node(
+                source,
+                14..19,
+                items(vec![&long_item, "b"]),
+                &PRETTIFY_DEFAULT_CONFIG
+            ),
+            format!(
+                "before\n  key: [\n      {},\n      b\n  ]  \nafter",
+                long_item
+            )
+        );
+        let config = PrettifyConfig {
+            print_width: 12,
+            end_of_line: EndOfLine::Auto,
+            ..PRETTIFY_DEFAULT_CONFIG
+        };
+        assert_eq!(
+            splice_formatted_node(
+                "before\r\n  key: [1,2]  \r\nafter",
+                15..20,
+                items(vec!["1", "2"]),
+                &config
+            ),
+            "before\r\n  key: [\r\n      1,\r\n      2\r\n  ]  \r\nafter"
+        );
+    }
+}
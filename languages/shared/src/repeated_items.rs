@@ -1,26 +1,32 @@
+use crate::{mark_source_token, SourceContext};
 use nom::{
     bytes::complete::tag,
     character::complete::multispace0,
     combinator::{map, opt},
-    multi::separated_list0,
-    sequence::{delimited, tuple},
+    sequence::tuple,
 };
 use prettify::{
-    break_parent, concat, group, if_break, indent, join, line, soft_line, string, PrettifyDoc,
+    break_parent, concat, get_label, group_with_options, if_break, indent, label, line, soft_line,
+    string, DocOptions, GroupIdAllocator, PrettifyDoc,
 };
 
 #[derive(PartialEq, Debug, Clone, Copy)]
-pub struct RepeatedItemsOptions<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>>> {
+pub struct RepeatedItemsOptions<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc>> {
     open_delimiter: &'a str,
     item_parser: F,
     separator: &'a str,
     close_delimiter: &'a str,
-    allow_trailing_separator: bool,
+    // Each collection gets its own group id, so a trailing separator only depends on whether
+    // its own group breaks.
+    allow_trailing_separator: Option<&'a GroupIdAllocator>,
     use_user_preferred_indentation: bool,
     use_space_around_delimiters: bool,
+    break_nested_collections: bool,
+    // Marks the delimiters and separators with the range of the source they came from.
+    source: Option<SourceContext<'a>>,
 }
 
-impl<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>>> RepeatedItemsOptions<'a, F> {
+impl<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc>> RepeatedItemsOptions<'a, F> {
     pub fn new(
         open_delimiter: &'a str,
         item_parser: F,
@@ -32,14 +38,16 @@ impl<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>>> RepeatedIt
             item_parser,
             separator,
             close_delimiter,
-            allow_trailing_separator: false,
+            allow_trailing_separator: None,
             use_user_preferred_indentation: false,
             use_space_around_delimiters: false,
+            break_nested_collections: false,
+            source: None,
         }
     }
 
-    pub fn allow_trailing_separator(mut self) -> Self {
-        self.allow_trailing_separator = true;
+    pub fn allow_trailing_separator(mut self, group_ids: &'a GroupIdAllocator) -> Self {
+        self.allow_trailing_separator = Some(group_ids);
         self
     }
 
@@ -52,36 +60,108 @@ impl<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>>> RepeatedIt
         self.use_space_around_delimiters = true;
         self
     }
+
+    // Like Prettier's arrays, breaks when there are several items and all of them are
+    // collections of the same kind with several items each, e.g., a matrix.
+    pub fn break_nested_collections(mut self) -> Self {
+        self.break_nested_collections = true;
+        self
+    }
+
+    pub fn with_source(mut self, source: Option<SourceContext<'a>>) -> Self {
+        self.source = source;
+        self
+    }
+}
+
+// The label of a collection with several items, so a parent collection can tell what its items
+// are with `get_label`.
+fn collection_label(open_delimiter: &str, close_delimiter: &str) -> String {
+    format!("collection {}{}", open_delimiter, close_delimiter)
+}
+
+fn are_nested_collections(items: &[PrettifyDoc]) -> bool {
+    match items.first().and_then(get_label) {
+        Some(first) if first.starts_with("collection ") => {
+            items.len() > 1 && items.iter().all(|item| get_label(item) == Some(first))
+        }
+        _ => false,
+    }
+}
+
+// Like `separated_list0`, but also returns the separators between the items.
+fn separated_items<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc>>(
+    separator: &'a str,
+    mut item_parser: F,
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, (Vec<PrettifyDoc>, Vec<&'a str>)> {
+    move |input: &'a str| {
+        let mut items = vec![];
+        let mut separators = vec![];
+        let mut input = match item_parser(input) {
+            Ok((remainder, item)) => {
+                items.push(item);
+                remainder
+            }
+            Err(nom::Err::Error(_)) => return Ok((input, (items, separators))),
+            Err(error) => return Err(error),
+        };
+        loop {
+            let (after_separator, (_, separator, _)) =
+                match tuple((multispace0, tag(separator), multispace0))(input) {
+                    Ok(result) => result,
+                    Err(nom::Err::Error(_)) => return Ok((input, (items, separators))),
+                    Err(error) => return Err(error),
+                };
+            match item_parser(after_separator) {
+                Ok((remainder, item)) => {
+                    items.push(item);
+                    separators.push(separator);
+                    input = remainder;
+                }
+                Err(nom::Err::Error(_)) => return Ok((input, (items, separators))),
+                Err(error) => return Err(error),
+            }
+        }
+    }
 }
 
-pub fn repeated_items<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>>>(
+pub fn repeated_items<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc>>(
     options: RepeatedItemsOptions<'a, F>,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
+    let source = options.source;
     map(
-        delimited(
+        tuple((
             tag(options.open_delimiter),
-            tuple((
-                multispace0,
-                separated_list0(
-                    tuple((multispace0, tag(options.separator), multispace0)),
-                    options.item_parser,
-                ),
-            )),
-            tuple((
-                multispace0,
-                opt(tag(options.separator)),
-                multispace0,
-                tag(options.close_delimiter),
-            )),
-        ),
+            multispace0,
+            separated_items(options.separator, options.item_parser),
+            multispace0,
+            opt(tag(options.separator)),
+            multispace0,
+            tag(options.close_delimiter),
+        )),
         move |result| {
-            let (initial_whitespace, items) = result;
+            let (
+                open_delimiter,
+                initial_whitespace,
+                (items, separators),
+                _,
+                trailing_separator,
+                _,
+                close_delimiter,
+            ) = result;
             let use_space_around_delimiters =
-                options.use_space_around_delimiters && items.len() > 0;
-            group(concat(vec![
-                string(options.open_delimiter),
+                options.use_space_around_delimiters && !items.is_empty();
+            let group_id = options
+                .allow_trailing_separator
+                .map(|group_ids| group_ids.allocate());
+            let should_break = (options.use_user_preferred_indentation
+                && initial_whitespace.contains("\n"))
+                || (options.break_nested_collections && are_nested_collections(&items));
+            let item_count = items.len();
+            let contents = concat(vec![
+                mark_source_token(source, open_delimiter, string(options.open_delimiter)),
                 indent(concat(vec![
-                    if options.use_user_preferred_indentation && initial_whitespace.contains("\n") {
+                    if should_break {
                         break_parent()
                     } else {
                         string("")
@@ -91,11 +171,35 @@ pub fn repeated_items<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc
                     } else {
                         soft_line()
                     },
-                    join(items, concat(vec![string(options.separator), line()])),
-                    if options.allow_trailing_separator {
-                        if_break(string(options.separator), string(""), "separator")
-                    } else {
-                        string("")
+                    concat(
+                        items
+                            .into_iter()
+                            .enumerate()
+                            .flat_map(|(index, item)| match index {
+                                0 => vec![item],
+                                _ => vec![
+                                    mark_source_token(
+                                        source,
+                                        separators[index - 1],
+                                        string(options.separator),
+                                    ),
+                                    line(),
+                                    item,
+                                ],
+                            })
+                            .collect(),
+                    ),
+                    {
+                        let trailing = match group_id {
+                            Some(group_id) => {
+                                if_break(string(options.separator), string(""), Some(group_id))
+                            }
+                            None => string(""),
+                        };
+                        match trailing_separator {
+                            Some(separator) => mark_source_token(source, separator, trailing),
+                            None => trailing,
+                        }
                     },
                 ])),
                 if use_space_around_delimiters {
@@ -103,8 +207,23 @@ pub fn repeated_items<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc
                 } else {
                     soft_line()
                 },
-                string(options.close_delimiter),
-            ]))
+                mark_source_token(source, close_delimiter, string(options.close_delimiter)),
+            ]);
+            let collection = group_with_options(
+                contents,
+                DocOptions {
+                    id: group_id,
+                    ..DocOptions::default()
+                },
+            );
+            if item_count > 1 {
+                label(
+                    collection_label(options.open_delimiter, options.close_delimiter),
+                    collection,
+                )
+            } else {
+                collection
+            }
         },
     )
 }
@@ -112,6 +231,7 @@ pub fn repeated_items<'a, F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc
 #[cfg(test)]
 mod test {
     use crate::assert_formatted;
+    use nom::branch::alt;
 
     use super::*;
 
@@ -184,10 +304,11 @@ mod test {
 
     #[test]
     fn allow_trailing_separator() {
+        let group_ids = GroupIdAllocator::new();
         assert_formatted(
             repeated_items(
                 RepeatedItemsOptions::new("{", map(tag("hello"), string), ",", "}")
-                    .allow_trailing_separator(),
+                    .allow_trailing_separator(&group_ids),
             )("{hello,hello}"),
             ("", "{hello, hello}"),
         );
@@ -197,7 +318,7 @@ mod test {
                 map(tag("hello"), string),
                 ",",
                 "}",
-            ).allow_trailing_separator())("{hello,hello,hello,hello,hello,hello,hello,hello,hello,hello,hello,hello}"),
+            ).allow_trailing_separator(&group_ids))("{hello,hello,hello,hello,hello,hello,hello,hello,hello,hello,hello,hello}"),
             ("", "{\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n}"),
         );
     }
@@ -228,4 +349,51 @@ mod test {
             ("", "{\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello\n}"),
         );
     }
+
+    #[test]
+    fn break_nested_collections() {
+        let inner = |open, close| {
+            repeated_items(RepeatedItemsOptions::new(
+                open,
+                map(tag("hello"), string),
+                ",",
+                close,
+            ))
+        };
+        let outer = || {
+            repeated_items(
+                RepeatedItemsOptions::new("[", alt((inner("[", "]"), inner("{", "}"))), ",", "]")
+                    .break_nested_collections(),
+            )
+        };
+        assert_formatted(
+            outer()("[[hello,hello],[hello,hello]]"),
+            ("", "[\n    [hello, hello],\n    [hello, hello]\n]"),
+        );
+        assert_formatted(
+            outer()("[[hello,hello],[hello]]"),
+            ("", "[[hello, hello], [hello]]"),
+        );
+        assert_formatted(
+            outer()("[[hello,hello],{hello,hello}]"),
+            ("", "[[hello, hello], {hello, hello}]"),
+        );
+        assert_formatted(outer()("[[hello,hello]]"), ("", "[[hello, hello]]"));
+    }
+
+    #[test]
+    fn allow_trailing_separator_in_nested_items() {
+        let group_ids = GroupIdAllocator::new();
+        let inner = repeated_items(
+            RepeatedItemsOptions::new("[", map(tag("hello"), string), ",", "]")
+                .allow_trailing_separator(&group_ids),
+        );
+        assert_formatted(
+            repeated_items(
+                RepeatedItemsOptions::new("{", alt((inner, map(tag("hello"), string))), ",", "}")
+                    .allow_trailing_separator(&group_ids),
+            )("{[hello,hello],hello,hello,hello,hello,hello,hello,hello,hello,hello,hello}"),
+            ("", "{\n    [hello, hello],\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n    hello,\n}"),
+        );
+    }
 }
@@ -0,0 +1,372 @@
+use prettify::{concat, cursor, source_span, string, PrettifyDoc};
+use std::{cell::RefCell, ops::Range};
+
+// The source that is being formatted and, optionally, the position of the user's cursor in
+// it. Parsers pass every token through it so each token is marked with the range of the
+// source it came from, and a `cursor()` doc is placed next to the token the cursor belongs to
+// so the printer can report where the cursor ended up in the formatted output. Parsers can
+// also record the ranges of nodes that can be formatted on their own, for range formatting.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct SourceContext<'a> {
+    source: &'a str,
+    cursor: Option<CursorPlacement>,
+    node_ranges: Option<&'a RefCell<Vec<Range<usize>>>>,
+    token_ranges: Option<&'a RefCell<Vec<Range<usize>>>>,
+}
+
+// The token the cursor is placed next to, and on which side of it.
+#[derive(PartialEq, Debug, Clone, Copy)]
+struct CursorPlacement {
+    offset: usize,
+    token: (usize, usize),
+    before: bool,
+}
+
+impl<'a> SourceContext<'a> {
+    pub fn new(source: &'a str) -> SourceContext<'a> {
+        SourceContext {
+            source,
+            cursor: None,
+            node_ranges: None,
+            token_ranges: None,
+        }
+    }
+
+    // Places the cursor at `offset` next to exactly one of `token_ranges`, which are the
+    // tokens recorded by a previous pass over the same source with `with_token_ranges`. The
+    // cursor goes after the token it is inside of or at the end of, otherwise before the next
+    // token when only whitespace separates them, otherwise after the previous token, so an
+    // offset on a delimiter or in a gap still gets a cursor.
+    pub fn with_cursor(mut self, offset: usize, token_ranges: &[Range<usize>]) -> Self {
+        let containing = token_ranges
+            .iter()
+            .filter(|token| token.start < offset && offset <= token.end)
+            .max_by_key(|token| token.start)
+            .map(|token| (token, false));
+        let next = || {
+            token_ranges
+                .iter()
+                .filter(|token| {
+                    offset <= token.start
+                        && self
+                            .source
+                            .get(offset..token.start)
+                            .is_some_and(|between| between.trim().is_empty())
+                })
+                .min_by_key(|token| token.start)
+                .map(|token| (token, true))
+        };
+        let previous = || {
+            token_ranges
+                .iter()
+                .filter(|token| token.end < offset)
+                .max_by_key(|token| token.end)
+                .map(|token| (token, false))
+        };
+        let first = || {
+            token_ranges
+                .iter()
+                .min_by_key(|token| token.start)
+                .map(|token| (token, true))
+        };
+        self.cursor = containing
+            .or_else(next)
+            .or_else(previous)
+            .or_else(first)
+            .map(|(token, before)| CursorPlacement {
+                offset,
+                token: (token.start, token.end),
+                before,
+            });
+        self
+    }
+
+    pub fn with_node_ranges(mut self, node_ranges: &'a RefCell<Vec<Range<usize>>>) -> Self {
+        self.node_ranges = Some(node_ranges);
+        self
+    }
+
+    // Records the range of every marked token, for placing a cursor with `with_cursor`.
+    pub fn with_token_ranges(mut self, token_ranges: &'a RefCell<Vec<Range<usize>>>) -> Self {
+        self.token_ranges = Some(token_ranges);
+        self
+    }
+
+    // Records the range of `node` if node ranges are being collected.
+    pub fn record_node(&self, node: &'a str) {
+        if let (Some(node_ranges), Some((start, end))) = (self.node_ranges, self.token_range(node))
+        {
+            node_ranges.borrow_mut().push(start..end);
+        }
+    }
+
+    // Returns the start and end offsets of `token` in the source, or `None` if `token` is
+    // not a slice of the source (e.g., a static string).
+    fn token_range(&self, token: &str) -> Option<(usize, usize)> {
+        let source_start = self.source.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+        if token_start < source_start
+            || token_start + token.len() > source_start + self.source.len()
+        {
+            return None;
+        }
+        let start = token_start - source_start;
+        Some((start, start + token.len()))
+    }
+
+    // Records the range of a token if token ranges are being collected.
+    fn record_token(&self, start: usize, end: usize) {
+        if let Some(token_ranges) = self.token_ranges {
+            token_ranges.borrow_mut().push(start..end);
+        }
+    }
+
+    // Marks `doc` with the range of `token`, and places the cursor before or after `doc` if
+    // it belongs to `token`.
+    pub fn mark_token(&self, token: &'a str, doc: PrettifyDoc) -> PrettifyDoc {
+        let (start, end) = match self.token_range(token) {
+            Some(range) => range,
+            None => return doc,
+        };
+        self.record_token(start, end);
+        let doc = source_span(doc, start..end);
+        match self.cursor {
+            Some(placement) if placement.token == (start, end) && placement.before => {
+                concat(vec![cursor(), doc])
+            }
+            Some(placement) if placement.token == (start, end) => concat(vec![doc, cursor()]),
+            _ => doc,
+        }
+    }
+
+    // Like `mark_token`, but for text that is printed verbatim, so the cursor can be placed
+    // at the exact character it was at.
+    pub fn mark_text(&self, text: &'a str) -> PrettifyDoc {
+        match (self.token_range(text), self.cursor) {
+            (Some((start, end)), Some(placement))
+                if placement.token == (start, end)
+                    && start < placement.offset
+                    && placement.offset < end
+                    && text.is_char_boundary(placement.offset - start) =>
+            {
+                self.record_token(start, end);
+                let (before, after) = text.split_at(placement.offset - start);
+                source_span(
+                    concat(vec![string(before), cursor(), string(after)]),
+                    start..end,
+                )
+            }
+            _ => self.mark_token(text, string(text)),
+        }
+    }
+}
+
+pub fn mark_source_token<'a>(
+    source: Option<SourceContext<'a>>,
+    token: &'a str,
+    doc: PrettifyDoc,
+) -> PrettifyDoc {
+    match source {
+        Some(source) => source.mark_token(token, doc),
+        None => doc,
+    }
+}
+
+pub fn mark_source_text<'a>(source: Option<SourceContext<'a>>, text: &'a str) -> PrettifyDoc {
+    match source {
+        Some(source) => source.mark_text(text),
+        None => string(text),
+    }
+}
+
+pub fn with_source<'a, F>(
+    source: Option<SourceContext<'a>>,
+    mut parser: F,
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc>
+where
+    F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc>,
+{
+    move |input: &'a str| {
+        let (remainder, doc) = parser(input)?;
+        match source {
+            Some(source) => {
+                let token = &input[..input.len() - remainder.len()];
+                Ok((remainder, source.mark_token(token, doc)))
+            }
+            None => Ok((remainder, doc)),
+        }
+    }
+}
+
+// Like `with_source`, but records the range of what `parser` consumed as a node instead of
+// marking it as a token.
+pub fn with_node<'a, F>(
+    source: Option<SourceContext<'a>>,
+    mut parser: F,
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc>
+where
+    F: FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc>,
+{
+    move |input: &'a str| {
+        let (remainder, doc) = parser(input)?;
+        if let Some(source) = source {
+            source.record_node(&input[..input.len() - remainder.len()]);
+        }
+        Ok((remainder, doc))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nom::{bytes::complete::tag, combinator::map};
+    use prettify::{join, print_with_cursor, PRETTIFY_DEFAULT_CONFIG};
+
+    fn cursor_offsets(doc: PrettifyDoc) -> (String, Vec<usize>) {
+        let printed = print_with_cursor(doc, &PRETTIFY_DEFAULT_CONFIG);
+        (
+            printed.formatted,
+            printed.cursors.iter().map(|cursor| cursor.byte).collect(),
+        )
+    }
+
+    fn mark_words(source: &str, offset: usize) -> (String, Vec<usize>) {
+        let words = |context: SourceContext| {
+            join(
+                source
+                    .split(' ')
+                    .filter(|word| !word.is_empty())
+                    .map(|word| context.mark_text(word))
+                    .collect(),
+                string(" "),
+            )
+        };
+        let token_ranges = RefCell::new(vec![]);
+        words(SourceContext::new(source).with_token_ranges(&token_ranges));
+        let token_ranges = token_ranges.into_inner();
+        cursor_offsets(words(
+            SourceContext::new(source).with_cursor(offset, &token_ranges),
+        ))
+    }
+
+    #[test]
+    fn mark_text_test() {
+        let source = "hello world";
+        let word = &source[6..];
+        let tokens = [0..5, 6..11];
+        assert_eq!(
+            cursor_offsets(
+                SourceContext::new(source)
+                    .with_cursor(8, &tokens)
+                    .mark_text(word)
+            ),
+            ("world".to_string(), vec![2])
+        );
+        assert_eq!(
+            cursor_offsets(
+                SourceContext::new(source)
+                    .with_cursor(11, &tokens)
+                    .mark_text(word)
+            ),
+            ("world".to_string(), vec![5])
+        );
+        assert_eq!(
+            cursor_offsets(
+                SourceContext::new(source)
+                    .with_cursor(3, &tokens)
+                    .mark_text(word)
+            ),
+            ("world".to_string(), vec![])
+        );
+        assert_eq!(
+            cursor_offsets(
+                SourceContext::new(source)
+                    .with_cursor(3, &tokens)
+                    .mark_text("world")
+            ),
+            ("world".to_string(), vec![])
+        );
+    }
+
+    #[test]
+    fn places_one_cursor_per_offset() {
+        let source = "hello  world";
+        let cursors: Vec<Vec<usize>> = (0..=source.len())
+            .map(|offset| mark_words(source, offset).1)
+            .collect();
+        assert_eq!(
+            cursors,
+            vec![
+                vec![0],
+                vec![1],
+                vec![2],
+                vec![3],
+                vec![4],
+                vec![5],
+                vec![6],
+                vec![6],
+                vec![7],
+                vec![8],
+                vec![9],
+                vec![10],
+                vec![11],
+            ]
+        );
+    }
+
+    #[test]
+    fn cursor_at_token_end_stays_after_token() {
+        assert_eq!(
+            mark_words("hello world", 5),
+            ("hello world".to_string(), vec![5])
+        );
+    }
+
+    #[test]
+    fn cursor_outside_of_tokens() {
+        let source = "(hello)  ";
+        let token = &source[1..6];
+        let tokens = std::slice::from_ref(&(1..6));
+        let mark = |offset| {
+            cursor_offsets(
+                SourceContext::new(source)
+                    .with_cursor(offset, tokens)
+                    .mark_text(token),
+            )
+        };
+        assert_eq!(mark(0), ("hello".to_string(), vec![0]));
+        assert_eq!(mark(6), ("hello".to_string(), vec![5]));
+        assert_eq!(mark(9), ("hello".to_string(), vec![5]));
+    }
+
+    #[test]
+    fn with_source_test() {
+        let source = "  hello";
+        let parser = |offset| {
+            with_source(
+                Some(SourceContext::new(source).with_cursor(offset, std::slice::from_ref(&(2..7)))),
+                map(tag("hello"), |_| string("HELLO")),
+            )
+        };
+        let result = parser(0)(&source[2..]).unwrap();
+        assert_eq!(cursor_offsets(result.1), ("HELLO".to_string(), vec![0]));
+        let result = parser(4)(&source[2..]).unwrap();
+        assert_eq!(cursor_offsets(result.1), ("HELLO".to_string(), vec![5]));
+        let result = with_source(None, map(tag("hello"), |_| string("HELLO")))(&source[2..]);
+        assert_eq!(
+            cursor_offsets(result.unwrap().1),
+            ("HELLO".to_string(), vec![])
+        );
+    }
+
+    #[test]
+    fn with_node_test() {
+        let source = "  hello";
+        let node_ranges = RefCell::new(vec![]);
+        let context = SourceContext::new(source).with_node_ranges(&node_ranges);
+        let result = with_node(Some(context), map(tag("hello"), string))(&source[2..]);
+        assert_eq!(result.unwrap().0, "");
+        assert!(with_node(Some(context), map(tag("world"), string))(&source[2..]).is_err());
+        assert_eq!(node_ranges.into_inner(), vec![2..7]);
+    }
+}
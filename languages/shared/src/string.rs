@@ -31,6 +31,12 @@ pub struct StringOptions<'a> {
     unicode_transform_lowercase: bool,
 }
 
+impl<'a> Default for StringOptions<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> StringOptions<'a> {
     pub fn new() -> StringOptions<'a> {
         StringOptions {
@@ -74,7 +80,7 @@ impl<'a> StringOptions<'a> {
     }
 }
 
-pub fn unicode_4_digit_escape_sequence(input: &str) -> nom::IResult<&str, StringFragment> {
+pub fn unicode_4_digit_escape_sequence(input: &str) -> nom::IResult<&str, StringFragment<'_>> {
     map(
         preceded(
             tag("\\u"),
@@ -84,7 +90,7 @@ pub fn unicode_4_digit_escape_sequence(input: &str) -> nom::IResult<&str, String
     )(input)
 }
 
-pub fn unicode_8_digit_escape_sequence(input: &str) -> nom::IResult<&str, StringFragment> {
+pub fn unicode_8_digit_escape_sequence(input: &str) -> nom::IResult<&str, StringFragment<'_>> {
     map(
         preceded(
             tag("\\U"),
@@ -94,26 +100,26 @@ pub fn unicode_8_digit_escape_sequence(input: &str) -> nom::IResult<&str, String
     )(input)
 }
 
-pub fn unicode_escape_sequence(input: &str) -> nom::IResult<&str, StringFragment> {
+pub fn unicode_escape_sequence(input: &str) -> nom::IResult<&str, StringFragment<'_>> {
     alt((
         unicode_4_digit_escape_sequence,
         unicode_8_digit_escape_sequence,
     ))(input)
 }
 
-pub fn backslash_escape(input: &str) -> nom::IResult<&str, StringFragment> {
+pub fn backslash_escape(input: &str) -> nom::IResult<&str, StringFragment<'_>> {
     map(
         preceded(tag("\\"), recognize(none_of("\n\r"))),
         StringFragment::Escaped,
     )(input)
 }
 
-pub fn unescaped_char(input: &str) -> nom::IResult<&str, StringFragment> {
+pub fn unescaped_char(input: &str) -> nom::IResult<&str, StringFragment<'_>> {
     map(recognize(none_of("\n\r")), StringFragment::Unescaped)(input)
 }
 
-pub fn unescaped_char_multiline(input: &str) -> nom::IResult<&str, StringFragment> {
-    map(take(1 as usize), StringFragment::Unescaped)(input)
+pub fn unescaped_char_multiline(input: &str) -> nom::IResult<&str, StringFragment<'_>> {
+    map(take(1_usize), StringFragment::Unescaped)(input)
 }
 
 pub fn parse_custom_quoted_string<'a>(
@@ -168,7 +174,7 @@ pub fn format_custom_quoted_string<'a>(
     quote: &'a str,
     fragments: Vec<StringFragment<'a>>,
     options: StringOptions<'a>,
-) -> PrettifyDoc<'a> {
+) -> PrettifyDoc {
     concat(vec![
         prettify_string(quote),
         concat(
@@ -204,7 +210,7 @@ pub fn format_custom_quoted_string<'a>(
 pub fn custom_quoted_string<'a>(
     quote: &'a str,
     options: StringOptions<'a>,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(parse_custom_quoted_string(quote, options), move |result| {
         format_custom_quoted_string(quote, result, options)
     })
@@ -219,13 +225,13 @@ pub fn parse_single_quoted_string<'a>(
 pub fn format_single_quoted_string<'a>(
     fragments: Vec<StringFragment<'a>>,
     options: StringOptions<'a>,
-) -> PrettifyDoc<'a> {
+) -> PrettifyDoc {
     format_custom_quoted_string("'", fragments, options)
 }
 
 pub fn single_quoted_string<'a>(
     options: StringOptions<'a>,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(parse_single_quoted_string(options), move |result| {
         format_single_quoted_string(result, options)
     })
@@ -240,13 +246,13 @@ pub fn parse_double_quoted_string<'a>(
 pub fn format_double_quoted_string<'a>(
     fragments: Vec<StringFragment<'a>>,
     options: StringOptions<'a>,
-) -> PrettifyDoc<'a> {
+) -> PrettifyDoc {
     format_custom_quoted_string("\"", fragments, options)
 }
 
 pub fn double_quoted_string<'a>(
     options: StringOptions<'a>,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(parse_double_quoted_string(options), move |result| {
         format_double_quoted_string(result, options)
     })
@@ -264,7 +270,7 @@ pub fn parse_string<'a>(
 pub fn format_string<'a>(
     fragments: Vec<StringFragment<'a>>,
     options: StringOptions<'a>,
-) -> PrettifyDoc<'a> {
+) -> PrettifyDoc {
     match options.force_quote_type {
         Some(QuoteType::Single) => format_single_quoted_string(fragments, options),
         Some(QuoteType::Double) => format_double_quoted_string(fragments, options),
@@ -291,7 +297,7 @@ pub fn format_string<'a>(
 
 pub fn parse_and_format_string<'a>(
     options: StringOptions<'a>,
-) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc<'a>> {
+) -> impl FnMut(&'a str) -> nom::IResult<&'a str, PrettifyDoc> {
     map(parse_string(options), move |result| {
         format_string(result, options)
     })
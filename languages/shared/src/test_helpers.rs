@@ -5,7 +5,7 @@ pub fn assert_errors<T>(response: nom::IResult<&str, T>) {
 }
 
 pub fn assert_formatted<'a>(
-    result: nom::IResult<&'a str, PrettifyDoc<'a>>,
+    result: nom::IResult<&'a str, PrettifyDoc>,
     (expected_remainder, expected_formatted): (&'a str, &'a str),
 ) {
     let (actual_remainder, actual_formatted) = print_result(result);
@@ -13,7 +13,7 @@ pub fn assert_formatted<'a>(
     assert_eq!(actual_remainder, expected_remainder);
 }
 
-pub fn print_result<'a>(result: nom::IResult<&'a str, PrettifyDoc<'a>>) -> (&'a str, String) {
+pub fn print_result(result: nom::IResult<&str, PrettifyDoc>) -> (&str, String) {
     let (remainder, doc) = result.unwrap();
     (remainder, print(doc))
 }
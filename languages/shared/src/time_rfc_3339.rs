@@ -9,7 +9,7 @@ use nom::{
 use prettify::{concat, string, PrettifyDoc};
 
 pub fn is_digit(chr: char) -> bool {
-    ('0'..='9').contains(&chr)
+    chr.is_ascii_digit()
 }
 
 pub fn rfc_3339_full_year(input: &str) -> nom::IResult<&str, PrettifyDoc> {
@@ -12,7 +12,7 @@ use prettify::{join, string, PrettifyDoc};
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct KeyValuePair<'a> {
-    pub prettify_doc: PrettifyDoc<'a>,
+    pub prettify_doc: PrettifyDoc,
     pub raw_key: Vec<&'a str>,
 }
 
@@ -22,7 +22,7 @@ pub fn key(input: &str) -> nom::IResult<&str, PrettifyDoc> {
     Ok((remainder, join(parts, string("."))))
 }
 
-pub fn raw_key(input: &str) -> nom::IResult<&str, KeyValuePair> {
+pub fn raw_key(input: &str) -> nom::IResult<&str, KeyValuePair<'_>> {
     let (remainder, parts) = separated_list1(
         delimiter,
         alt((recognize(single_line_string), raw_bare_key)),
@@ -1,7 +1,15 @@
-use prettify::string;
+use nom::{
+    combinator::{all_consuming, eof, not},
+    multi::many0,
+    sequence::preceded,
+};
+use prettify::{concat, string, PrettifyConfig};
+use prettify_shared::{find_overlapping_nodes, splice_formatted_node};
+use table::{table, table_ranges};
 
 extern crate nom;
 extern crate prettify;
+extern crate prettify_shared;
 
 mod boolean;
 mod helpers;
@@ -14,3 +22,49 @@ mod table;
 pub fn format_toml(_toml: &str) -> Result<prettify::PrettifyDoc, &str> {
     Ok(string("hello"))
 }
+
+// Formats every table that `start..end` touches and leaves the rest of `toml` as it is.
+// `toml` is returned unchanged when the range touches no table.
+pub fn format_range<'a>(
+    toml: &'a str,
+    start: usize,
+    end: usize,
+    config: &PrettifyConfig,
+) -> Result<String, &'a str> {
+    let node = match find_overlapping_nodes(&table_ranges(toml), start, end) {
+        Some(node) => node,
+        None => return Ok(toml.to_string()),
+    };
+    match all_consuming(many0(preceded(not(eof), table)))(&toml[node.clone()]) {
+        Ok((_, tables)) => Ok(splice_formatted_node(toml, node, concat(tables), config)),
+        Err(_) => Err("Invalid toml"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use prettify::PRETTIFY_DEFAULT_CONFIG;
+
+    #[test]
+    fn format_range_test() {
+        let toml = "a=1\n\n[ b ]\nc  =  true\n[[d]]\ne='f'  #comment\n";
+        assert_eq!(
+            format_range(toml, 12, 13, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "a=1\n\n[b]\nc = true\n[[d]]\ne='f'  #comment\n"
+        );
+        assert_eq!(
+            format_range(toml, 0, 12, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "a = 1\n\n[b]\nc = true\n[[d]]\ne='f'  #comment\n"
+        );
+        assert_eq!(
+            format_range(toml, 26, 26, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            "a=1\n\n[ b ]\nc  =  true\n[[d]]\ne = 'f' # comment\n"
+        );
+        assert_eq!(
+            format_range(toml, 100, 100, &PRETTIFY_DEFAULT_CONFIG).unwrap(),
+            toml
+        );
+        assert!(format_range("a = [1, 2]\n", 0, 1, &PRETTIFY_DEFAULT_CONFIG).is_err());
+    }
+}
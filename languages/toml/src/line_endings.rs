@@ -10,7 +10,7 @@ use prettify::{concat, hard_line, string, PrettifyDoc};
 fn comment(input: &str) -> nom::IResult<&str, PrettifyDoc> {
     map(preceded(tag("#"), not_line_ending), |result: &str| {
         let trimmed = result.trim();
-        if trimmed.len() > 0 {
+        if !trimmed.is_empty() {
             string(format!("# {}", trimmed))
         } else {
             string("")
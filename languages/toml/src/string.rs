@@ -69,7 +69,7 @@ pub fn multi_line_string(input: &str) -> nom::IResult<&str, PrettifyDoc> {
         map(
             recognize(tuple((
                 tag("'''"),
-                many_till(take(1 as usize), tuple((tag("'''"), not(tag("'"))))),
+                many_till(take(1_usize), tuple((tag("'''"), not(tag("'"))))),
             ))),
             string,
         ),
@@ -2,19 +2,22 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::space0,
-    combinator::{map, recognize},
+    combinator::{eof, map, not, opt, recognize},
+    multi::many0,
     sequence::{preceded, tuple},
 };
 use prettify::{concat, string, PrettifyDoc};
+use std::ops::Range;
 
 use crate::{
     key::{key, raw_key, KeyValuePair},
-    line_endings::line_end_with_optional_comment,
+    key_value_pair::key_value_pair,
+    line_endings::{blank_line_with_optional_comment, line_end_with_optional_comment},
 };
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct TableHeader<'a> {
-    pub key: PrettifyDoc<'a>,
+    pub key: PrettifyDoc,
     pub raw_key: KeyValuePair<'a>,
     pub repeated: bool,
 }
@@ -23,7 +26,7 @@ pub fn table_header(input: &str) -> nom::IResult<&str, PrettifyDoc> {
     map(raw_table_header, |result| result.key)(input)
 }
 
-pub fn raw_table_header(input: &str) -> nom::IResult<&str, TableHeader> {
+pub fn raw_table_header(input: &str) -> nom::IResult<&str, TableHeader<'_>> {
     alt((raw_repeated_table_header, raw_unrepeated_table_header))(input)
 }
 
@@ -50,7 +53,7 @@ fn parsed_unrepeated_table_header(input: &str) -> nom::IResult<&str, PrettifyDoc
     ))
 }
 
-fn raw_unrepeated_table_header(input: &str) -> nom::IResult<&str, TableHeader> {
+fn raw_unrepeated_table_header(input: &str) -> nom::IResult<&str, TableHeader<'_>> {
     let (remainder, (_, raw_key, _)) = tuple((
         opening_unrepeated_delimiter,
         raw_key,
@@ -89,7 +92,7 @@ fn parsed_repeated_table_header(input: &str) -> nom::IResult<&str, PrettifyDoc>
     ))
 }
 
-fn raw_repeated_table_header(input: &str) -> nom::IResult<&str, TableHeader> {
+fn raw_repeated_table_header(input: &str) -> nom::IResult<&str, TableHeader<'_>> {
     let (remainder, (_, raw_key, _)) = tuple((
         opening_repeated_delimiter,
         raw_key,
@@ -105,6 +108,41 @@ fn raw_repeated_table_header(input: &str) -> nom::IResult<&str, TableHeader> {
     ))
 }
 
+// A table header and the lines after it, up to the next header. The lines before the first
+// header belong to the root table, which has no header.
+pub fn table(input: &str) -> nom::IResult<&str, PrettifyDoc> {
+    map(
+        tuple((
+            opt(table_header),
+            many0(preceded(
+                not(eof),
+                alt((key_value_pair, blank_line_with_optional_comment)),
+            )),
+        )),
+        |(header, mut lines)| {
+            if let Some(header) = header {
+                lines.insert(0, header);
+            }
+            concat(lines)
+        },
+    )(input)
+}
+
+// Returns the range of each table, from its header to the next one. Headers are found line by
+// line, so a header-like line in a multi-line string splits a table in two.
+pub fn table_ranges(toml: &str) -> Vec<Range<usize>> {
+    let mut starts = vec![0];
+    let mut offset = 0;
+    for line in toml.split_inclusive('\n') {
+        if offset > 0 && raw_table_header(line).is_ok() {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+    starts.push(toml.len());
+    starts.windows(2).map(|range| range[0]..range[1]).collect()
+}
+
 #[cfg(test)]
 mod test {
     use prettify_shared::assert_formatted;
@@ -115,31 +153,31 @@ mod test {
     fn raw_table_header_test() {
         let header = raw_table_header("[foo]").unwrap().1;
         assert_eq!(header.raw_key.raw_key, vec!["foo"]);
-        assert_eq!(header.repeated, false);
+        assert!(!header.repeated);
 
         let header = raw_table_header("[[foo]]").unwrap().1;
         assert_eq!(header.raw_key.raw_key, vec!["foo"]);
-        assert_eq!(header.repeated, true);
+        assert!(header.repeated);
 
         let header = raw_table_header("[foo]\n").unwrap().1;
         assert_eq!(header.raw_key.raw_key, vec!["foo"]);
-        assert_eq!(header.repeated, false);
+        assert!(!header.repeated);
 
         let header = raw_table_header("[[foo]]\n").unwrap().1;
         assert_eq!(header.raw_key.raw_key, vec!["foo"]);
-        assert_eq!(header.repeated, true);
+        assert!(header.repeated);
 
         let header = raw_table_header("[foo.bar]").unwrap().1;
         assert_eq!(header.raw_key.raw_key, vec!["foo", "bar"]);
-        assert_eq!(header.repeated, false);
+        assert!(!header.repeated);
 
         let header = raw_table_header("[[foo.bar]]").unwrap().1;
         assert_eq!(header.raw_key.raw_key, vec!["foo", "bar"]);
-        assert_eq!(header.repeated, true);
+        assert!(header.repeated);
 
         let header = raw_table_header("[foo.\"bar.fizz.buzz\"]").unwrap().1;
         assert_eq!(header.raw_key.raw_key, vec!["foo", "\"bar.fizz.buzz\""]);
-        assert_eq!(header.repeated, false);
+        assert!(!header.repeated);
     }
 
     #[test]
@@ -154,4 +192,26 @@ mod test {
             ("", "[hello.\"world\"] # this is a comment\n"),
         );
     }
+
+    #[test]
+    fn table_test() {
+        assert_formatted(
+            table("[ hello ]\nworld=true  # comment\n\nfoo  =  'bar'\n[next]\n"),
+            (
+                "[next]\n",
+                "[hello]\nworld = true # comment\n\nfoo = 'bar'\n",
+            ),
+        );
+        assert_formatted(table("a=1\n"), ("", "a = 1\n"));
+    }
+
+    #[test]
+    fn table_ranges_test() {
+        assert_eq!(
+            table_ranges("a = 1\n[b]\nc = 2\n\n[[d]]\n"),
+            vec![0..6, 6..17, 17..23]
+        );
+        assert_eq!(table_ranges("[b]\nc = 2"), vec![0..9]);
+        assert_eq!(table_ranges(""), vec![0..0]);
+    }
 }
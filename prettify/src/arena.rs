@@ -0,0 +1,605 @@
+use super::doc::{AlignAmount, Doc, DocCommand, DocPointer, GroupId, LineMode};
+use crate::doc_utils::clean_doc;
+use crate::print::group_breaks;
+use crate::PrettifyDoc;
+use std::collections::HashMap;
+use std::ops::Range;
+
+// The index of a doc in a `DocArena`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct DocId(usize);
+
+// Text that is either a slice of the arena's source or stored in the arena's own buffer.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) enum TextSpan {
+    Source(usize, usize),
+    Interned(usize, usize),
+}
+
+// A range of doc ids in the arena's `children` buffer, used for concats, fills and the
+// expanded states of conditional groups.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) struct DocRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl DocRange {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn slice(&self, start: usize, end: usize) -> DocRange {
+        DocRange {
+            start: self.start + start,
+            end: self.start + end,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) enum ArenaAlign {
+    Spaces(usize),
+    String(TextSpan),
+    DedentToRoot,
+    Dedent,
+    IndentBy(usize),
+    MarkAsRoot,
+    DedentToMark,
+}
+
+// Same as `Doc`, but children are referenced by index and text by span so every doc is
+// `Copy`.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) enum ArenaDoc {
+    String(TextSpan),
+    Children(DocRange),
+    // `should_break` already includes breaks propagated from the contents.
+    Group {
+        contents: DocId,
+        should_break: bool,
+        id: Option<GroupId>,
+        expanded_states: DocRange,
+    },
+    Fill(DocRange),
+    IfBreak(DocId, DocId, Option<GroupId>),
+    IndentIfBreak(DocId, GroupId, bool),
+    BreakParent,
+    Line(LineMode),
+    LineSuffix(DocId),
+    LineSuffixBoundary,
+    Indent(DocId),
+    Align(DocId, ArenaAlign),
+    Cursor,
+    Trim,
+    SourceSpan(DocId, usize, usize),
+    // Only created by the printer, to mark where the contents of a `SourceSpan` end.
+    SourceSpanEnd(usize),
+}
+
+// Stores docs in flat buffers instead of allocating each one separately. Text that is a
+// slice of `source` is stored as a span of it instead of being copied.
+//
+// Docs can only reference docs that were added before them, so breaks are propagated to
+// groups as they are added instead of in a separate pass.
+#[derive(Debug, Clone)]
+pub struct DocArena<'a> {
+    source: &'a str,
+    text: String,
+    docs: Vec<ArenaDoc>,
+    contains_break: Vec<bool>,
+    children: Vec<DocId>,
+    // One more than the largest group id used, so the printer can size its group mode table.
+    group_id_count: usize,
+}
+
+impl<'a> DocArena<'a> {
+    pub fn new(source: &'a str) -> DocArena<'a> {
+        DocArena {
+            source,
+            text: String::new(),
+            docs: Vec::new(),
+            contains_break: Vec::new(),
+            children: Vec::new(),
+            group_id_count: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    pub(crate) fn get(&self, id: DocId) -> ArenaDoc {
+        self.docs[id.0]
+    }
+
+    pub(crate) fn get_children(&self, range: DocRange) -> &[DocId] {
+        &self.children[range.start..range.end]
+    }
+
+    pub(crate) fn get_text(&self, span: TextSpan) -> &str {
+        match span {
+            TextSpan::Source(start, end) => &self.source[start..end],
+            TextSpan::Interned(start, end) => &self.text[start..end],
+        }
+    }
+
+    pub(crate) fn get_align_amount(&self, amount: ArenaAlign) -> AlignAmount {
+        match amount {
+            ArenaAlign::Spaces(spaces) => AlignAmount::Spaces(spaces),
+            ArenaAlign::String(span) => AlignAmount::String(self.get_text(span).to_string()),
+            ArenaAlign::DedentToRoot => AlignAmount::DedentToRoot,
+            ArenaAlign::Dedent => AlignAmount::Dedent,
+            ArenaAlign::IndentBy(width) => AlignAmount::IndentBy(width),
+            ArenaAlign::MarkAsRoot => AlignAmount::MarkAsRoot,
+            ArenaAlign::DedentToMark => AlignAmount::DedentToMark,
+        }
+    }
+
+    pub(crate) fn group_id_count(&self) -> usize {
+        self.group_id_count
+    }
+
+    fn push(&mut self, doc: ArenaDoc, contains_break: bool) -> DocId {
+        self.docs.push(doc);
+        self.contains_break.push(contains_break);
+        DocId(self.docs.len() - 1)
+    }
+
+    fn push_children(&mut self, docs: &[DocId]) -> (DocRange, bool) {
+        let start = self.children.len();
+        self.children.extend_from_slice(docs);
+        let contains_break = docs.iter().any(|doc| self.contains_break[doc.0]);
+        (
+            DocRange {
+                start,
+                end: self.children.len(),
+            },
+            contains_break,
+        )
+    }
+
+    fn intern_text(&mut self, text: &str) -> TextSpan {
+        let source_start = self.source.as_ptr() as usize;
+        let text_start = text.as_ptr() as usize;
+        if text_start >= source_start && text_start + text.len() <= source_start + self.source.len()
+        {
+            let start = text_start - source_start;
+            return TextSpan::Source(start, start + text.len());
+        }
+        let start = self.text.len();
+        self.text.push_str(text);
+        TextSpan::Interned(start, self.text.len())
+    }
+
+    fn use_group_id(&mut self, id: GroupId) {
+        self.group_id_count = self.group_id_count.max(id.index() + 1);
+    }
+
+    fn contains_break(&self, doc: DocId) -> bool {
+        self.contains_break[doc.0]
+    }
+
+    pub fn string(&mut self, text: &str) -> DocId {
+        let span = self.intern_text(text);
+        self.push(ArenaDoc::String(span), false)
+    }
+
+    pub fn concat(&mut self, docs: Vec<DocId>) -> DocId {
+        let (range, contains_break) = self.push_children(&docs);
+        self.push(ArenaDoc::Children(range), contains_break)
+    }
+
+    pub fn join(&mut self, docs: Vec<DocId>, separator: DocId) -> DocId {
+        let mut children = Vec::with_capacity(docs.len() * 2);
+        for (index, doc) in docs.into_iter().enumerate() {
+            if index > 0 {
+                children.push(separator);
+            }
+            children.push(doc);
+        }
+        self.concat(children)
+    }
+
+    fn group_with_break(
+        &mut self,
+        contents: DocId,
+        should_break: bool,
+        id: Option<GroupId>,
+        expanded_states: &[DocId],
+    ) -> DocId {
+        if let Some(id) = id {
+            self.use_group_id(id);
+        }
+        let (expanded_states, _) = self.push_children(expanded_states);
+        let should_break = group_breaks(
+            should_break,
+            !expanded_states.is_empty(),
+            self.contains_break(contents),
+        );
+        self.push(
+            ArenaDoc::Group {
+                contents,
+                should_break,
+                id,
+                expanded_states,
+            },
+            should_break,
+        )
+    }
+
+    pub fn group(&mut self, doc: DocId) -> DocId {
+        self.group_with_break(doc, false, None, &[])
+    }
+
+    pub fn group_with_id(&mut self, doc: DocId, id: GroupId) -> DocId {
+        self.group_with_break(doc, false, Some(id), &[])
+    }
+
+    pub fn conditional_group(&mut self, docs: Vec<DocId>, id: Option<GroupId>) -> DocId {
+        if docs.is_empty() {
+            panic!("conditional_group requires at least one doc");
+        }
+        self.group_with_break(docs[0], false, id, &docs)
+    }
+
+    pub fn fill(&mut self, docs: Vec<DocId>) -> DocId {
+        let (range, contains_break) = self.push_children(&docs);
+        self.push(ArenaDoc::Fill(range), contains_break)
+    }
+
+    pub fn if_break(
+        &mut self,
+        break_contents: DocId,
+        flat_contents: DocId,
+        group_id: Option<GroupId>,
+    ) -> DocId {
+        if let Some(id) = group_id {
+            self.use_group_id(id);
+        }
+        let contains_break =
+            self.contains_break(break_contents) || self.contains_break(flat_contents);
+        self.push(
+            ArenaDoc::IfBreak(break_contents, flat_contents, group_id),
+            contains_break,
+        )
+    }
+
+    pub fn indent_if_break(&mut self, contents: DocId, group_id: GroupId, negate: bool) -> DocId {
+        self.use_group_id(group_id);
+        let contains_break = self.contains_break(contents);
+        self.push(
+            ArenaDoc::IndentIfBreak(contents, group_id, negate),
+            contains_break,
+        )
+    }
+
+    pub fn indent(&mut self, doc: DocId) -> DocId {
+        let contains_break = self.contains_break(doc);
+        self.push(ArenaDoc::Indent(doc), contains_break)
+    }
+
+    pub fn indent_by(&mut self, width: usize, doc: DocId) -> DocId {
+        self.align(doc, AlignAmount::IndentBy(width))
+    }
+
+    pub fn align(&mut self, doc: DocId, amount: AlignAmount) -> DocId {
+        let amount = match amount {
+            AlignAmount::Spaces(spaces) => ArenaAlign::Spaces(spaces),
+            AlignAmount::String(string) => ArenaAlign::String(self.intern_text(&string)),
+            AlignAmount::DedentToRoot => ArenaAlign::DedentToRoot,
+            AlignAmount::Dedent => ArenaAlign::Dedent,
+            AlignAmount::IndentBy(width) => ArenaAlign::IndentBy(width),
+            AlignAmount::MarkAsRoot => ArenaAlign::MarkAsRoot,
+            AlignAmount::DedentToMark => ArenaAlign::DedentToMark,
+        };
+        let contains_break = self.contains_break(doc);
+        self.push(ArenaDoc::Align(doc, amount), contains_break)
+    }
+
+    pub fn dedent(&mut self, doc: DocId) -> DocId {
+        self.align(doc, AlignAmount::Dedent)
+    }
+
+    pub fn dedent_to_root(&mut self, doc: DocId) -> DocId {
+        self.align(doc, AlignAmount::DedentToRoot)
+    }
+
+    pub fn mark_as_root(&mut self, doc: DocId) -> DocId {
+        self.align(doc, AlignAmount::MarkAsRoot)
+    }
+
+    pub fn dedent_to_mark(&mut self, doc: DocId) -> DocId {
+        self.align(doc, AlignAmount::DedentToMark)
+    }
+
+    pub fn break_parent(&mut self) -> DocId {
+        self.push(ArenaDoc::BreakParent, true)
+    }
+
+    pub fn line(&mut self) -> DocId {
+        self.push(ArenaDoc::Line(LineMode::Auto), false)
+    }
+
+    pub fn soft_line(&mut self) -> DocId {
+        self.push(ArenaDoc::Line(LineMode::Soft), false)
+    }
+
+    pub fn hard_line_without_break_parent(&mut self) -> DocId {
+        self.push(ArenaDoc::Line(LineMode::Hard), false)
+    }
+
+    pub fn literal_line_without_break_parent(&mut self) -> DocId {
+        self.push(ArenaDoc::Line(LineMode::HardLiteral), false)
+    }
+
+    pub fn hard_line(&mut self) -> DocId {
+        let line = self.hard_line_without_break_parent();
+        let break_parent = self.break_parent();
+        self.concat(vec![line, break_parent])
+    }
+
+    pub fn literal_line(&mut self) -> DocId {
+        let line = self.literal_line_without_break_parent();
+        let break_parent = self.break_parent();
+        self.concat(vec![line, break_parent])
+    }
+
+    pub fn line_suffix(&mut self, contents: DocId) -> DocId {
+        let contains_break = self.contains_break(contents);
+        self.push(ArenaDoc::LineSuffix(contents), contains_break)
+    }
+
+    pub fn line_suffix_boundary(&mut self) -> DocId {
+        self.push(ArenaDoc::LineSuffixBoundary, false)
+    }
+
+    pub fn cursor(&mut self) -> DocId {
+        self.push(ArenaDoc::Cursor, false)
+    }
+
+    pub fn trim(&mut self) -> DocId {
+        self.push(ArenaDoc::Trim, false)
+    }
+
+    pub fn source_span(&mut self, contents: DocId, range: Range<usize>) -> DocId {
+        let contains_break = self.contains_break(contents);
+        self.push(
+            ArenaDoc::SourceSpan(contents, range.start, range.end),
+            contains_break,
+        )
+    }
+
+    // Adds a doc made with the builders to the arena. Docs that are shared between several
+    // parents are only added once. The doc is cleaned with `clean_doc` first, so docs that
+    // don't affect the output aren't added.
+    pub fn import(&mut self, doc: &PrettifyDoc) -> DocId {
+        let doc = &clean_doc(doc);
+        enum Step<'b> {
+            Enter(&'b PrettifyDoc),
+            // Exit(doc, number of children)
+            Exit(&'b PrettifyDoc, usize),
+        }
+        let mut shared: HashMap<*const Doc, DocId> = HashMap::new();
+        let mut ids: Vec<DocId> = Vec::new();
+        let mut steps = vec![Step::Enter(doc)];
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Enter(doc) => {
+                    let is_shared = DocPointer::strong_count(doc) > 1;
+                    if is_shared {
+                        if let Some(id) = shared.get(&DocPointer::as_ptr(doc)) {
+                            ids.push(*id);
+                            continue;
+                        }
+                    }
+                    if let Some(id) = self.import_source_text(doc) {
+                        ids.push(id);
+                        continue;
+                    }
+                    let children = doc.get_children();
+                    steps.push(Step::Exit(doc, children.len()));
+                    for child in children.into_iter().rev() {
+                        steps.push(Step::Enter(child));
+                    }
+                }
+                Step::Exit(doc, child_count) => {
+                    let first_child = ids.len() - child_count;
+                    let id = self.import_with_children(doc, &ids[first_child..]);
+                    ids.truncate(first_child);
+                    if DocPointer::strong_count(doc) > 1 {
+                        shared.insert(DocPointer::as_ptr(doc), id);
+                    }
+                    ids.push(id);
+                }
+            }
+        }
+        ids.pop().unwrap()
+    }
+
+    // A string marked with the range of the source it's a copy of is stored as a span of the
+    // source instead of being copied into the arena.
+    fn import_source_text(&mut self, doc: &Doc) -> Option<DocId> {
+        match doc {
+            Doc::Command(DocCommand::SourceSpan(contents, range)) => match contents.as_ref() {
+                Doc::String(text) if self.source.get(range.clone()) == Some(text.as_str()) => {
+                    let string = self.push(
+                        ArenaDoc::String(TextSpan::Source(range.start, range.end)),
+                        false,
+                    );
+                    Some(self.source_span(string, range.clone()))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // `children` are the imported ids of `doc.get_children()`.
+    fn import_with_children(&mut self, doc: &Doc, children: &[DocId]) -> DocId {
+        match doc {
+            Doc::String(string) => self.string(string),
+            Doc::Children(_) => self.concat(children.to_vec()),
+            Doc::Command(command) => match command {
+                DocCommand::Group(_, options) => self.group_with_break(
+                    children[0],
+                    options.should_break,
+                    options.id,
+                    &children[1..],
+                ),
+                DocCommand::Fill(_, _) => self.fill(children.to_vec()),
+                DocCommand::IfBreak(_, _, group_id) => {
+                    self.if_break(children[0], children[1], *group_id)
+                }
+                DocCommand::IndentIfBreak(_, group_id, negate) => {
+                    self.indent_if_break(children[0], *group_id, *negate)
+                }
+                DocCommand::Indent(_) => self.indent(children[0]),
+                DocCommand::Align(_, amount) => self.align(children[0], amount.clone()),
+                DocCommand::BreakParent => self.break_parent(),
+                DocCommand::Line(line_mode) => self.push(ArenaDoc::Line(*line_mode), false),
+                DocCommand::LineSuffix(_) => self.line_suffix(children[0]),
+                DocCommand::LineSuffixBoundary => self.line_suffix_boundary(),
+                DocCommand::Cursor => self.cursor(),
+                DocCommand::Trim => self.trim(),
+                DocCommand::SourceSpan(_, range) => self.source_span(children[0], range.clone()),
+                // Labels don't change the output, so the printer only needs their contents.
+                DocCommand::Label(_, _) => children[0],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{concat, group, line, source_span, string};
+
+    #[test]
+    fn string_test() {
+        let source = "hello world";
+        let mut arena = DocArena::new(source);
+        let from_source = arena.string(&source[6..]);
+        let owned = arena.string("hello");
+        assert_eq!(
+            arena.get(from_source),
+            ArenaDoc::String(TextSpan::Source(6, 11))
+        );
+        assert_eq!(arena.get(owned), ArenaDoc::String(TextSpan::Interned(0, 5)));
+        assert_eq!(arena.get_text(TextSpan::Source(6, 11)), "world");
+        assert_eq!(arena.get_text(TextSpan::Interned(0, 5)), "hello");
+    }
+
+    #[test]
+    fn import_test() {
+        let shared = group(concat(vec![string("a"), line(), string("b")]));
+        let doc = concat(vec![PrettifyDoc::clone(&shared), shared]);
+        let mut arena = DocArena::new("");
+        let root = arena.import(&doc);
+        assert_eq!(arena.len(), 6);
+        match arena.get(root) {
+            ArenaDoc::Children(range) => {
+                let children = arena.get_children(range);
+                assert_eq!(children[0], children[1]);
+            }
+            _ => panic!("expected the root to be a concat"),
+        }
+    }
+
+    #[test]
+    fn import_source_text_test() {
+        let source = "[hello, 'world']";
+        let doc = concat(vec![
+            source_span(string("hello"), 1..6),
+            source_span(string("\"world\""), 8..15),
+        ]);
+        let mut arena = DocArena::new(source);
+        let root = arena.import(&doc);
+        let texts: Vec<ArenaDoc> = match arena.get(root) {
+            ArenaDoc::Children(range) => arena
+                .get_children(range)
+                .iter()
+                .map(|child| match arena.get(*child) {
+                    ArenaDoc::SourceSpan(contents, _, _) => arena.get(contents),
+                    _ => panic!("expected a source span"),
+                })
+                .collect(),
+            _ => panic!("expected the root to be a concat"),
+        };
+        assert_eq!(
+            texts,
+            vec![
+                ArenaDoc::String(TextSpan::Source(1, 6)),
+                ArenaDoc::String(TextSpan::Interned(0, 7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn import_cleans_doc_test() {
+        let source = "abc";
+        let inner = group(concat(vec![string("x"), line()]));
+        let doc = concat(vec![
+            string(&source[0..1]),
+            concat(vec![string(""), string(&source[1..2]), string("d")]),
+            group(string("")),
+            group(inner),
+            string("e"),
+        ]);
+        let mut arena = DocArena::new(source);
+        let root = arena.import(&doc);
+        let children = match arena.get(root) {
+            ArenaDoc::Children(range) => arena.get_children(range).to_vec(),
+            _ => panic!("expected the root to be a concat"),
+        };
+        assert_eq!(children.len(), 3);
+        match arena.get(children[0]) {
+            ArenaDoc::String(span) => assert_eq!(arena.get_text(span), "abd"),
+            _ => panic!("expected the strings to be merged"),
+        }
+        match arena.get(children[1]) {
+            ArenaDoc::Group { contents, .. } => {
+                assert!(matches!(arena.get(contents), ArenaDoc::Children(_)))
+            }
+            _ => panic!("expected the nested groups to be unwrapped"),
+        }
+        match arena.get(children[2]) {
+            ArenaDoc::String(span) => assert_eq!(arena.get_text(span), "e"),
+            _ => panic!("expected a string"),
+        }
+    }
+
+    #[test]
+    fn propagates_breaks_test() {
+        let mut arena = DocArena::new("");
+        let hard_line = arena.hard_line();
+        let inner = arena.group(hard_line);
+        let outer = arena.group(inner);
+        let text = arena.string("text");
+        let conditional = arena.conditional_group(vec![outer, text], None);
+        for id in [inner, outer] {
+            assert!(matches!(
+                arena.get(id),
+                ArenaDoc::Group {
+                    should_break: true,
+                    ..
+                }
+            ));
+        }
+        assert!(matches!(
+            arena.get(conditional),
+            ArenaDoc::Group {
+                should_break: false,
+                ..
+            }
+        ));
+    }
+}
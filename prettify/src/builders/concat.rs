@@ -1,7 +1,6 @@
-use super::super::doc::Doc;
+use super::super::doc::{Doc, DocPointer};
 use crate::PrettifyDoc;
-use std::rc::Rc;
 
 pub fn concat(docs: Vec<PrettifyDoc>) -> PrettifyDoc {
-    Rc::new(Doc::Children(docs))
+    DocPointer::new(Doc::Children(docs))
 }
@@ -1,7 +1,6 @@
-use super::super::doc::{Doc, DocCommand};
+use super::super::doc::{Doc, DocCommand, DocPointer};
 use crate::PrettifyDoc;
-use std::rc::Rc;
 
-pub fn cursor<'a>() -> PrettifyDoc<'a> {
-    Rc::new(Doc::Command(DocCommand::Cursor))
+pub fn cursor() -> PrettifyDoc {
+    DocPointer::new(Doc::Command(DocCommand::Cursor))
 }
@@ -6,6 +6,10 @@ pub fn dedent_to_root(doc: PrettifyDoc) -> PrettifyDoc {
     align(doc, AlignAmount::DedentToRoot)
 }
 
+pub fn dedent_to_mark(doc: PrettifyDoc) -> PrettifyDoc {
+    align(doc, AlignAmount::DedentToMark)
+}
+
 pub fn dedent(doc: PrettifyDoc) -> PrettifyDoc {
     align(doc, AlignAmount::Dedent)
 }
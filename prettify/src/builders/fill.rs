@@ -1,14 +1,9 @@
-use super::super::doc::{Doc, DocCommand, DocOptions};
+use super::super::doc::{Doc, DocCommand, DocOptions, DocPointer};
 use crate::PrettifyDoc;
-use std::rc::Rc;
 
 pub fn fill(docs: Vec<PrettifyDoc>) -> PrettifyDoc {
-    Rc::new(Doc::Command(DocCommand::Fill(
+    DocPointer::new(Doc::Command(DocCommand::Fill(
         docs,
-        Rc::new(DocOptions {
-            id: "",
-            should_break: false,
-            expanded_states: vec![],
-        }),
+        DocPointer::new(DocOptions::default()),
     )))
 }
@@ -1,27 +1,27 @@
-use super::super::doc::{Doc, DocCommand};
+use super::super::doc::{Doc, DocCommand, DocPointer, GroupId};
 use crate::PrettifyDoc;
-use std::rc::Rc;
 
-pub fn if_break<'a, S: Into<String>>(
-    break_contents: PrettifyDoc<'a>,
-    flat_contents: PrettifyDoc<'a>,
-    group_id: S,
-) -> PrettifyDoc<'a> {
-    Rc::new(Doc::Command(DocCommand::IfBreak(
+// Without a group id, the mode of the enclosing group decides which contents are printed.
+pub fn if_break(
+    break_contents: PrettifyDoc,
+    flat_contents: PrettifyDoc,
+    group_id: Option<GroupId>,
+) -> PrettifyDoc {
+    DocPointer::new(Doc::Command(DocCommand::IfBreak(
         break_contents,
         flat_contents,
-        group_id.into(),
+        group_id,
     )))
 }
 
-pub fn indent_if_break<S: Into<String>>(
+pub fn indent_if_break(
     break_contents: PrettifyDoc,
-    group_id: S,
+    group_id: GroupId,
     negate: bool,
 ) -> PrettifyDoc {
-    Rc::new(Doc::Command(DocCommand::IndentIfBreak(
+    DocPointer::new(Doc::Command(DocCommand::IndentIfBreak(
         break_contents,
-        group_id.into(),
+        group_id,
         negate,
     )))
 }
@@ -1,7 +1,13 @@
-use super::super::doc::{Doc, DocCommand};
+use super::super::doc::{AlignAmount, Doc, DocCommand, DocPointer};
+use super::align::align;
 use crate::PrettifyDoc;
-use std::rc::Rc;
 
 pub fn indent(doc: PrettifyDoc) -> PrettifyDoc {
-    Rc::new(Doc::Command(DocCommand::Indent(doc)))
+    DocPointer::new(Doc::Command(DocCommand::Indent(doc)))
+}
+
+// Indents `doc` by `width` columns instead of `tab_width`. When using tabs, the columns only
+// become a tab when an indent follows them.
+pub fn indent_by(width: usize, doc: PrettifyDoc) -> PrettifyDoc {
+    align(doc, AlignAmount::IndentBy(width))
 }
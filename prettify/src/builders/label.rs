@@ -0,0 +1,19 @@
+use super::super::doc::{Doc, DocCommand, DocPointer};
+use crate::PrettifyDoc;
+
+// Port of Prettier's label. Lets a formatter check what kind of doc a child produced, e.g.,
+// "member-chain", without changing how it prints. An empty label returns `contents` as is.
+pub fn label<S: Into<String>>(label: S, contents: PrettifyDoc) -> PrettifyDoc {
+    let label = label.into();
+    if label.is_empty() {
+        return contents;
+    }
+    DocPointer::new(Doc::Command(DocCommand::Label(label, contents)))
+}
+
+pub fn get_label(doc: &PrettifyDoc) -> Option<&str> {
+    match doc.as_ref() {
+        Doc::Command(DocCommand::Label(label, _)) => Some(label),
+        _ => None,
+    }
+}
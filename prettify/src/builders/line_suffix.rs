@@ -1,11 +1,10 @@
-use super::super::doc::{Doc, DocCommand};
+use super::super::doc::{Doc, DocCommand, DocPointer};
 use crate::PrettifyDoc;
-use std::rc::Rc;
 
-pub fn line_suffix(str: &str) -> PrettifyDoc {
-    Rc::new(Doc::Command(DocCommand::LineSuffix(str)))
+pub fn line_suffix(contents: PrettifyDoc) -> PrettifyDoc {
+    DocPointer::new(Doc::Command(DocCommand::LineSuffix(contents)))
 }
 
-pub fn line_suffix_boundary<'a>() -> PrettifyDoc<'a> {
-    Rc::new(Doc::Command(DocCommand::LineSuffixBoundary))
+pub fn line_suffix_boundary() -> PrettifyDoc {
+    DocPointer::new(Doc::Command(DocCommand::LineSuffixBoundary))
 }
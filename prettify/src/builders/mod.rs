@@ -8,25 +8,29 @@ mod group;
 mod if_break;
 mod indent;
 mod join;
+mod label;
 mod line;
 mod line_suffix;
+mod source_span;
 mod string;
 mod trim;
 
-pub use align::{add_alignment_to_doc, align};
+pub use align::{align, mark_as_root};
 pub use break_parent::break_parent;
 pub use concat::concat;
 pub use cursor::cursor;
-pub use dedent::{dedent, dedent_to_root};
+pub use dedent::{dedent, dedent_to_mark, dedent_to_root};
 pub use fill::fill;
 pub use group::{conditional_group, group, group_with_options};
 pub use if_break::{if_break, indent_if_break};
-pub use indent::indent;
+pub use indent::{indent, indent_by};
 pub use join::{join, join_to_vector};
+pub use label::{get_label, label};
 pub use line::{
     hard_line, hard_line_without_break_parent, line, literal_line,
     literal_line_without_break_parent, soft_line,
 };
 pub use line_suffix::{line_suffix, line_suffix_boundary};
+pub use source_span::source_span;
 pub use string::string;
 pub use trim::trim;
@@ -0,0 +1,9 @@
+use super::super::doc::{Doc, DocCommand, DocPointer};
+use crate::PrettifyDoc;
+use std::ops::Range;
+
+// Marks the byte range of the source that `contents` were created from. It doesn't change the
+// output, but `print_with_source_map` reports where it ended up.
+pub fn source_span(contents: PrettifyDoc, range: Range<usize>) -> PrettifyDoc {
+    DocPointer::new(Doc::Command(DocCommand::SourceSpan(contents, range)))
+}
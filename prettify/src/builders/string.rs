@@ -1,7 +1,6 @@
-use super::super::doc::Doc;
+use super::super::doc::{Doc, DocPointer};
 use crate::PrettifyDoc;
-use std::rc::Rc;
 
-pub fn string<'a, S: Into<String>>(str: S) -> PrettifyDoc<'a> {
-    Rc::new(Doc::String(str.into()))
+pub fn string<S: Into<String>>(str: S) -> PrettifyDoc {
+    DocPointer::new(Doc::String(str.into()))
 }
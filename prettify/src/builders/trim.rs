@@ -1,7 +1,6 @@
-use super::super::doc::{Doc, DocCommand};
+use super::super::doc::{Doc, DocCommand, DocPointer};
 use crate::PrettifyDoc;
-use std::rc::Rc;
 
-pub fn trim<'a>() -> PrettifyDoc<'a> {
-    Rc::new(Doc::Command(DocCommand::Trim))
+pub fn trim() -> PrettifyDoc {
+    DocPointer::new(Doc::Command(DocCommand::Trim))
 }
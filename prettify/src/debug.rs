@@ -0,0 +1,279 @@
+use super::doc::{AlignAmount, Doc, DocCommand, DocPointer, GroupId, LineMode};
+use crate::{concat, group, hard_line, indent, join, line, print, soft_line, string, PrettifyDoc};
+use std::collections::HashMap;
+
+fn call(name: &str, arguments: Vec<PrettifyDoc>) -> PrettifyDoc {
+    list(&format!("{}(", name), arguments, ")")
+}
+
+fn list(open: &str, items: Vec<PrettifyDoc>, close: &str) -> PrettifyDoc {
+    if items.is_empty() {
+        return string(format!("{}{}", open, close));
+    }
+    group(concat(vec![
+        string(open),
+        indent(concat(vec![
+            soft_line(),
+            join(items, concat(vec![string(","), line()])),
+        ])),
+        soft_line(),
+        string(close),
+    ]))
+}
+
+// Returns the docs that are rendered as arguments of `doc`. A conditional group is rendered
+// from its expanded states, which already include its contents.
+fn get_rendered_children(doc: &Doc) -> Vec<&PrettifyDoc> {
+    match doc {
+        Doc::Command(DocCommand::Group(_, options)) if !options.expanded_states.is_empty() => {
+            options.expanded_states.iter().collect()
+        }
+        _ if get_line_name(doc).is_some() => vec![],
+        _ => doc.get_children().collect(),
+    }
+}
+
+// `hard_line()` and `literal_line()` create a concat, so they are detected by their parts.
+fn get_line_name(doc: &Doc) -> Option<&'static str> {
+    match doc {
+        Doc::Children(children) if children.len() == 2 => {
+            match (children[0].as_ref(), children[1].as_ref()) {
+                (
+                    Doc::Command(DocCommand::Line(LineMode::Hard)),
+                    Doc::Command(DocCommand::BreakParent),
+                ) => Some("hard_line"),
+                (
+                    Doc::Command(DocCommand::Line(LineMode::HardLiteral)),
+                    Doc::Command(DocCommand::BreakParent),
+                ) => Some("literal_line"),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+struct DebugPrinter {
+    // How many times each doc with children is referenced.
+    references: HashMap<*const Doc, usize>,
+    labels: HashMap<*const Doc, String>,
+    bindings: Vec<PrettifyDoc>,
+    group_ids: Vec<GroupId>,
+}
+
+impl DebugPrinter {
+    fn count_references(&mut self, doc: &PrettifyDoc) {
+        let mut docs = vec![doc];
+        while let Some(doc) = docs.pop() {
+            let children = get_rendered_children(doc);
+            if children.is_empty() {
+                continue;
+            }
+            let count = self.references.entry(DocPointer::as_ptr(doc)).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                docs.extend(children);
+            }
+        }
+    }
+
+    // Docs that are referenced more than once are rendered as a `let` binding before the
+    // root doc, so it's clear they are shared.
+    fn render(&mut self, doc: &PrettifyDoc) -> PrettifyDoc {
+        let pointer = DocPointer::as_ptr(doc);
+        if self.references.get(&pointer).copied().unwrap_or(0) <= 1 {
+            return self.render_doc(doc);
+        }
+        if let Some(label) = self.labels.get(&pointer) {
+            return string(format!("PrettifyDoc::clone(&{})", label));
+        }
+        let rendered = self.render_doc(doc);
+        let label = format!("shared_{}", self.labels.len());
+        self.bindings.push(concat(vec![
+            string(format!("let {} = ", label)),
+            rendered,
+            string(";"),
+        ]));
+        let reference = string(format!("PrettifyDoc::clone(&{})", label));
+        self.labels.insert(pointer, label);
+        reference
+    }
+
+    // Group ids are rendered as variables in the order they first appear, since their values
+    // are opaque.
+    fn render_group_id(&mut self, id: GroupId) -> String {
+        let index = match self.group_ids.iter().position(|group_id| *group_id == id) {
+            Some(index) => index,
+            None => {
+                self.group_ids.push(id);
+                self.group_ids.len() - 1
+            }
+        };
+        format!("group_id_{}", index)
+    }
+
+    fn render_optional_group_id(&mut self, id: Option<GroupId>) -> String {
+        match id {
+            Some(id) => format!("Some({})", self.render_group_id(id)),
+            None => "None".to_string(),
+        }
+    }
+
+    fn render_all(&mut self, docs: &[PrettifyDoc]) -> Vec<PrettifyDoc> {
+        docs.iter().map(|doc| self.render(doc)).collect()
+    }
+
+    fn render_doc(&mut self, doc: &PrettifyDoc) -> PrettifyDoc {
+        if let Some(name) = get_line_name(doc) {
+            return call(name, vec![]);
+        }
+        match doc.as_ref() {
+            Doc::String(text) => call("string", vec![string(format!("{:?}", text))]),
+            Doc::Children(children) => {
+                let children = self.render_all(children);
+                list("concat(vec![", children, "])")
+            }
+            Doc::Command(command) => match command {
+                DocCommand::Group(contents, options) => {
+                    if !options.expanded_states.is_empty() {
+                        let states = self.render_all(&options.expanded_states);
+                        let id = self.render_optional_group_id(options.id);
+                        call(
+                            "conditional_group",
+                            vec![list("vec![", states, "]"), string(id)],
+                        )
+                    } else if !options.should_break && options.id.is_none() {
+                        let contents = self.render(contents);
+                        call("group", vec![contents])
+                    } else {
+                        let contents = self.render(contents);
+                        let id = self.render_optional_group_id(options.id);
+                        call(
+                            "group_with_options",
+                            vec![
+                                contents,
+                                list(
+                                    "DocOptions {",
+                                    vec![
+                                        string(format!("should_break: {}", options.should_break)),
+                                        string(format!("id: {}", id)),
+                                        string("expanded_states: vec![]"),
+                                    ],
+                                    "}",
+                                ),
+                            ],
+                        )
+                    }
+                }
+                DocCommand::Fill(contents, _) => {
+                    let contents = self.render_all(contents);
+                    list("fill(vec![", contents, "])")
+                }
+                DocCommand::IfBreak(break_contents, flat_contents, group_id) => {
+                    let break_contents = self.render(break_contents);
+                    let flat_contents = self.render(flat_contents);
+                    let group_id = self.render_optional_group_id(*group_id);
+                    call(
+                        "if_break",
+                        vec![break_contents, flat_contents, string(group_id)],
+                    )
+                }
+                DocCommand::IndentIfBreak(contents, group_id, negate) => {
+                    let contents = self.render(contents);
+                    let group_id = self.render_group_id(*group_id);
+                    call(
+                        "indent_if_break",
+                        vec![contents, string(group_id), string(negate.to_string())],
+                    )
+                }
+                DocCommand::Indent(contents) => {
+                    let contents = self.render(contents);
+                    call("indent", vec![contents])
+                }
+                DocCommand::Align(contents, amount) => {
+                    let contents = self.render(contents);
+                    match amount {
+                        AlignAmount::DedentToRoot => call("dedent_to_root", vec![contents]),
+                        AlignAmount::Dedent => call("dedent", vec![contents]),
+                        AlignAmount::IndentBy(width) => {
+                            call("indent_by", vec![string(width.to_string()), contents])
+                        }
+                        AlignAmount::MarkAsRoot => call("mark_as_root", vec![contents]),
+                        AlignAmount::DedentToMark => call("dedent_to_mark", vec![contents]),
+                        AlignAmount::Spaces(spaces) => call(
+                            "align",
+                            vec![contents, string(format!("AlignAmount::Spaces({})", spaces))],
+                        ),
+                        AlignAmount::String(text) => call(
+                            "align",
+                            vec![
+                                contents,
+                                string(format!("AlignAmount::String({:?}.to_string())", text)),
+                            ],
+                        ),
+                    }
+                }
+                DocCommand::BreakParent => call("break_parent", vec![]),
+                DocCommand::Line(line_mode) => call(
+                    match line_mode {
+                        LineMode::Auto => "line",
+                        LineMode::Soft => "soft_line",
+                        LineMode::Hard => "hard_line_without_break_parent",
+                        LineMode::HardLiteral => "literal_line_without_break_parent",
+                    },
+                    vec![],
+                ),
+                DocCommand::LineSuffix(contents) => {
+                    let contents = self.render(contents);
+                    call("line_suffix", vec![contents])
+                }
+                DocCommand::LineSuffixBoundary => call("line_suffix_boundary", vec![]),
+                DocCommand::Cursor => call("cursor", vec![]),
+                DocCommand::Trim => call("trim", vec![]),
+                DocCommand::SourceSpan(contents, range) => {
+                    let contents = self.render(contents);
+                    call(
+                        "source_span",
+                        vec![contents, string(format!("{:?}", range))],
+                    )
+                }
+                DocCommand::Label(label, contents) => {
+                    let contents = self.render(contents);
+                    call("label", vec![string(format!("{:?}", label)), contents])
+                }
+            },
+        }
+    }
+}
+
+// Port of Prettier's printDocToDebug:
+// https://github.com/prettier/prettier/blob/main/src/document/debug.js
+// Renders `doc` as the builder calls that create it.
+pub fn print_doc_to_debug(doc: PrettifyDoc) -> String {
+    let mut printer = DebugPrinter {
+        references: HashMap::new(),
+        labels: HashMap::new(),
+        bindings: Vec::new(),
+        group_ids: Vec::new(),
+    };
+    printer.count_references(&doc);
+    let root = printer.render(&doc);
+    let mut parts = Vec::new();
+    if !printer.group_ids.is_empty() {
+        parts.push(string("let group_ids = GroupIdAllocator::new();"));
+        parts.push(hard_line());
+        for index in 0..printer.group_ids.len() {
+            parts.push(string(format!(
+                "let group_id_{} = group_ids.allocate();",
+                index
+            )));
+            parts.push(hard_line());
+        }
+    }
+    for binding in printer.bindings.drain(..) {
+        parts.push(binding);
+        parts.push(hard_line());
+    }
+    parts.push(root);
+    print(concat(parts))
+}
@@ -1,6 +1,7 @@
-use std::rc::Rc;
+use std::cell::Cell;
+use std::ops::Range;
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum LineMode {
     Hard,
     Soft,
@@ -8,50 +9,277 @@ pub enum LineMode {
     HardLiteral,
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum AlignAmount {
     Spaces(usize),
     String(String),
     DedentToRoot,
     Dedent,
+    // An indent that is this many columns wide instead of `tab_width`. When using tabs, it
+    // stays spaces unless an indent follows it, like other alignments.
+    IndentBy(usize),
+    // Marks the current indentation, like Prettier's `markAsRoot`, so `DedentToMark` can
+    // return to it, e.g., for a document embedded in an indented block.
+    MarkAsRoot,
+    // Goes back to the indentation of the closest `MarkAsRoot`, or to the root without one.
+    DedentToMark,
 }
 
-pub type Contents<'a> = Rc<Doc<'a>>;
+// Docs are shared through `Rc` by default. The `sync` feature switches to `Arc` so docs can be
+// built on one thread and printed on another.
+#[cfg(not(feature = "sync"))]
+pub type DocPointer<T> = std::rc::Rc<T>;
+#[cfg(feature = "sync")]
+pub type DocPointer<T> = std::sync::Arc<T>;
+
+pub type Contents = DocPointer<Doc>;
 
 #[derive(PartialEq, Debug, Clone)]
-pub enum DocCommand<'a> {
-    Group(Contents<'a>, Rc<DocOptions<'a>>),
-    Fill(Vec<Rc<Doc<'a>>>, Rc<DocOptions<'a>>),
-    IfBreak(Contents<'a>, Contents<'a>, String),
+pub enum DocCommand {
+    Group(Contents, DocPointer<DocOptions>),
+    Fill(Vec<DocPointer<Doc>>, DocPointer<DocOptions>),
+    IfBreak(Contents, Contents, Option<GroupId>),
     // IndentIfBreak(Contents, group_id, negate)
-    IndentIfBreak(Contents<'a>, String, bool),
+    IndentIfBreak(Contents, GroupId, bool),
     BreakParent,
     Line(LineMode),
-    LineSuffix(&'a str),
+    LineSuffix(Contents),
     LineSuffixBoundary,
-    Indent(Contents<'a>),
-    Align(Contents<'a>, AlignAmount),
+    Indent(Contents),
+    Align(Contents, AlignAmount),
     Cursor,
     Trim,
+    // Label(label, contents) marks what kind of doc `contents` is without changing the output.
+    Label(String, Contents),
+    // SourceSpan(contents, range) marks the range of the source `contents` were created from,
+    // so the printer can map the output back to the source.
+    SourceSpan(Contents, Range<usize>),
 }
 
-#[derive(PartialEq, Debug, Clone)]
+// Names a group so `if_break` and `indent_if_break` can look up whether it was broken.
+// Ids are only unique within the `GroupIdAllocator` that created them.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct GroupId(usize);
+
+impl GroupId {
+    pub(crate) fn index(self) -> usize {
+        self.0
+    }
+}
+
+// Hands out group ids for one document. It only needs a shared reference, so parsers can
+// capture it in their closures.
+#[derive(PartialEq, Debug, Default)]
+pub struct GroupIdAllocator {
+    next: Cell<usize>,
+}
+
+impl GroupIdAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allocate(&self) -> GroupId {
+        let id = self.next.get();
+        self.next.set(id + 1);
+        GroupId(id)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Default)]
 // or Doc Opt for short…
 // …didn't see that Spider-man reference coming, did you?
-pub struct DocOptions<'a> {
+pub struct DocOptions {
     pub should_break: bool,
-    pub id: &'a str,
-    pub expanded_states: Vec<Rc<Doc<'a>>>,
+    pub id: Option<GroupId>,
+    pub expanded_states: Vec<DocPointer<Doc>>,
 }
 
 #[derive(PartialEq, Debug, Clone)]
-pub enum Doc<'a> {
+pub enum Doc {
     String(String),
-    Children(Vec<Rc<Doc<'a>>>),
-    Command(DocCommand<'a>),
+    Children(Vec<DocPointer<Doc>>),
+    Command(DocCommand),
+}
+
+impl Doc {
+    // Returns every doc directly inside of this one, including the expanded states of a
+    // conditional group.
+    pub(crate) fn get_children(&self) -> DocChildren<'_> {
+        let (first, second, rest): (_, _, &[DocPointer<Doc>]) = match self {
+            Doc::String(_) => (None, None, &[]),
+            Doc::Children(children) => (None, None, children),
+            Doc::Command(command) => match command {
+                DocCommand::Group(contents, options) => {
+                    (Some(contents), None, &options.expanded_states)
+                }
+                DocCommand::Fill(contents, _) => (None, None, contents),
+                DocCommand::IfBreak(break_contents, flat_contents, _) => {
+                    (Some(break_contents), Some(flat_contents), &[])
+                }
+                DocCommand::IndentIfBreak(contents, _, _)
+                | DocCommand::LineSuffix(contents)
+                | DocCommand::Indent(contents)
+                | DocCommand::Align(contents, _)
+                | DocCommand::Label(_, contents)
+                | DocCommand::SourceSpan(contents, _) => (Some(contents), None, &[]),
+                DocCommand::BreakParent
+                | DocCommand::Line(_)
+                | DocCommand::LineSuffixBoundary
+                | DocCommand::Cursor
+                | DocCommand::Trim => (None, None, &[]),
+            },
+        };
+        DocChildren {
+            first,
+            second,
+            rest: rest.iter(),
+        }
+    }
+
+    // Returns a copy of this doc with its children replaced, in the order of `get_children`.
+    pub(crate) fn with_children(&self, children: Vec<DocPointer<Doc>>) -> Doc {
+        let mut children = children.into_iter();
+        let mut next = || children.next().unwrap();
+        match self {
+            Doc::String(string) => Doc::String(string.clone()),
+            Doc::Children(_) => Doc::Children(children.collect()),
+            Doc::Command(command) => Doc::Command(match command {
+                DocCommand::Group(_, options) => {
+                    let contents = next();
+                    DocCommand::Group(
+                        contents,
+                        DocPointer::new(DocOptions {
+                            should_break: options.should_break,
+                            id: options.id,
+                            expanded_states: children.collect(),
+                        }),
+                    )
+                }
+                DocCommand::Fill(_, options) => {
+                    DocCommand::Fill(children.collect(), DocPointer::clone(options))
+                }
+                DocCommand::IfBreak(_, _, group_id) => {
+                    let break_contents = next();
+                    DocCommand::IfBreak(break_contents, next(), *group_id)
+                }
+                DocCommand::IndentIfBreak(_, group_id, negate) => {
+                    DocCommand::IndentIfBreak(next(), *group_id, *negate)
+                }
+                DocCommand::LineSuffix(_) => DocCommand::LineSuffix(next()),
+                DocCommand::Indent(_) => DocCommand::Indent(next()),
+                DocCommand::Align(_, amount) => DocCommand::Align(next(), amount.clone()),
+                DocCommand::Label(label, _) => DocCommand::Label(label.clone(), next()),
+                DocCommand::SourceSpan(_, range) => DocCommand::SourceSpan(next(), range.clone()),
+                command => command.clone(),
+            }),
+        }
+    }
+}
+
+// Iterates over the children of a doc without collecting them, see `Doc::get_children`.
+pub(crate) struct DocChildren<'a> {
+    pub(crate) first: Option<&'a DocPointer<Doc>>,
+    pub(crate) second: Option<&'a DocPointer<Doc>>,
+    pub(crate) rest: std::slice::Iter<'a, DocPointer<Doc>>,
+}
+
+impl<'a> Iterator for DocChildren<'a> {
+    type Item = &'a DocPointer<Doc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first
+            .take()
+            .or_else(|| self.second.take())
+            .or_else(|| self.rest.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.is_some() as usize + self.second.is_some() as usize + self.rest.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for DocChildren<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.rest
+            .next_back()
+            .or_else(|| self.second.take())
+            .or_else(|| self.first.take())
+    }
+}
+
+impl ExactSizeIterator for DocChildren<'_> {}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum EndOfLine {
+    Lf,
+    Crlf,
+    Cr,
+    // Uses the line ending that appears most often in the input. See `resolve_end_of_line`.
+    Auto,
+}
+
+// How the printer chooses between the layouts a doc allows, e.g., whether a group breaks.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum LayoutEngine {
+    // Like Prettier, each group is printed flat if it fits on the rest of its line. It only
+    // looks ahead to the next possible line break, so a later sibling can still overflow.
+    Greedy,
+    // Chooses the layout of the whole doc with the lowest `LayoutCost`. It's slower than the
+    // greedy engine and is only used when printing a `PrettifyDoc`, not a `DocArena`.
+    Optimal,
 }
 
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub struct PrettifyConfig {
     pub tab_width: usize,
+    pub print_width: usize,
+    pub use_tabs: bool,
+    pub end_of_line: EndOfLine,
+    pub layout_engine: LayoutEngine,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct CursorOffset {
+    pub byte: usize,
+    pub char: usize,
+}
+
+// Byte ranges of the printed output and the source it was created from.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SourceMapping {
+    pub output: Range<usize>,
+    pub input: Range<usize>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct SourceMappedDoc {
+    pub formatted: String,
+    pub mappings: Vec<SourceMapping>,
+}
+
+// A printed line that is still wider than the print width, e.g., because of a long URL that
+// can't be broken.
+#[derive(PartialEq, Debug, Clone)]
+pub struct LineWidthDiagnostic {
+    // The index of the line in the output, starting at 0.
+    pub line: usize,
+    pub width: usize,
+    // The contents of the `Doc::String` that pushed the line past the print width, and the
+    // byte range of the output it was printed at.
+    pub string: String,
+    pub output: Range<usize>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct DiagnosedDoc {
+    pub formatted: String,
+    pub diagnostics: Vec<LineWidthDiagnostic>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct PrintedDoc {
+    pub formatted: String,
+    pub cursors: Vec<CursorOffset>,
 }
@@ -0,0 +1,207 @@
+use super::doc::{Doc, DocChildren, DocCommand, DocPointer};
+use crate::{string, PrettifyDoc};
+use std::collections::HashMap;
+
+// Conditional groups are traversed through their expanded states, which include their contents.
+pub(crate) fn get_traversed_children(doc: &PrettifyDoc) -> DocChildren<'_> {
+    match doc.as_ref() {
+        Doc::Command(DocCommand::Group(_, options)) if !options.expanded_states.is_empty() => {
+            DocChildren {
+                first: None,
+                second: None,
+                rest: options.expanded_states.iter(),
+            }
+        }
+        _ => doc.get_children(),
+    }
+}
+
+// Port of Prettier's traverseDoc:
+// https://github.com/prettier/prettier/blob/main/src/document/utils.js
+// Calls `on_enter` before the children of a doc and `on_exit` after them. Returning `false`
+// from `on_enter` skips the children of that doc. Conditional groups are traversed through
+// their expanded states, which include their contents. Shared docs are visited every time
+// they appear.
+pub fn traverse_doc<E: FnMut(&PrettifyDoc) -> bool, X: FnMut(&PrettifyDoc)>(
+    doc: &PrettifyDoc,
+    mut on_enter: E,
+    mut on_exit: X,
+) {
+    // (doc, whether its children have been traversed)
+    let mut stack = vec![(doc, false)];
+    while let Some((current, exiting)) = stack.pop() {
+        if exiting {
+            on_exit(current);
+            continue;
+        }
+        if !on_enter(current) {
+            continue;
+        }
+        stack.push((current, true));
+        for child in get_traversed_children(current).rev() {
+            stack.push((child, false));
+        }
+    }
+}
+
+// Port of Prettier's mapDoc:
+// https://github.com/prettier/prettier/blob/main/src/document/utils.js
+// Rebuilds `doc` bottom-up. `callback` gets each doc after its children were mapped and returns
+// its replacement. Docs whose children didn't change are passed as is, and shared subtrees are
+// only mapped once so they stay shared.
+pub fn map_doc<F: FnMut(&PrettifyDoc) -> PrettifyDoc>(
+    doc: &PrettifyDoc,
+    mut callback: F,
+) -> PrettifyDoc {
+    enum Step<'b> {
+        Enter(&'b PrettifyDoc),
+        // Exit(doc, number of children)
+        Exit(&'b PrettifyDoc, usize),
+    }
+    // Only docs with several owners can be reached more than once, so only they are looked up.
+    let mut shared: HashMap<*const Doc, PrettifyDoc> = HashMap::new();
+    let mut mapped: Vec<PrettifyDoc> = Vec::new();
+    let mut steps = vec![Step::Enter(doc)];
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Enter(current) => {
+                if DocPointer::strong_count(current) > 1 {
+                    if let Some(result) = shared.get(&DocPointer::as_ptr(current)) {
+                        mapped.push(PrettifyDoc::clone(result));
+                        continue;
+                    }
+                }
+                let children = current.get_children();
+                steps.push(Step::Exit(current, children.len()));
+                for child in children.rev() {
+                    steps.push(Step::Enter(child));
+                }
+            }
+            Step::Exit(current, child_count) => {
+                let first_child = mapped.len() - child_count;
+                let changed = current
+                    .get_children()
+                    .zip(&mapped[first_child..])
+                    .any(|(child, new_child)| !DocPointer::ptr_eq(child, new_child));
+                let rebuilt = if changed {
+                    DocPointer::new(current.with_children(mapped.split_off(first_child)))
+                } else {
+                    mapped.truncate(first_child);
+                    PrettifyDoc::clone(current)
+                };
+                let result = callback(&rebuilt);
+                if DocPointer::strong_count(current) > 1 {
+                    shared.insert(DocPointer::as_ptr(current), PrettifyDoc::clone(&result));
+                }
+                mapped.push(result);
+            }
+        }
+    }
+    mapped.pop().unwrap()
+}
+
+fn is_empty_string(doc: &PrettifyDoc) -> bool {
+    matches!(doc.as_ref(), Doc::String(string) if string.is_empty())
+}
+
+// Replaces the last `run_length` strings of `parts` with one string of `run_text`.
+fn end_string_run(parts: &mut Vec<PrettifyDoc>, run_length: &mut usize, run_text: &mut String) {
+    if *run_length > 1 {
+        parts.truncate(parts.len() - *run_length);
+        parts.push(string(std::mem::take(run_text)));
+    }
+    *run_length = 0;
+    run_text.clear();
+}
+
+// Flattens nested concats, merges adjacent strings and drops empty ones. A run of strings is
+// merged into a single buffer.
+fn clean_children(doc: &PrettifyDoc, children: &[PrettifyDoc]) -> PrettifyDoc {
+    let mut parts: Vec<PrettifyDoc> = Vec::with_capacity(children.len());
+    let mut changed = false;
+    // The strings at the end of `parts` that will be merged, and their text.
+    let mut run_length = 0;
+    let mut run_text = String::new();
+    for child in children {
+        let inner = match child.as_ref() {
+            Doc::Children(inner) => {
+                changed = true;
+                inner.as_slice()
+            }
+            _ => std::slice::from_ref(child),
+        };
+        for part in inner {
+            match part.as_ref() {
+                Doc::String(text) if text.is_empty() => changed = true,
+                Doc::String(text) => {
+                    if run_length > 0 {
+                        changed = true;
+                    }
+                    run_length += 1;
+                    run_text.push_str(text);
+                    parts.push(PrettifyDoc::clone(part));
+                }
+                _ => {
+                    end_string_run(&mut parts, &mut run_length, &mut run_text);
+                    parts.push(PrettifyDoc::clone(part));
+                }
+            }
+        }
+    }
+    end_string_run(&mut parts, &mut run_length, &mut run_text);
+    match parts.len() {
+        0 => string(""),
+        1 => parts.pop().unwrap(),
+        _ if !changed => PrettifyDoc::clone(doc),
+        _ => DocPointer::new(Doc::Children(parts)),
+    }
+}
+
+fn clean(doc: &PrettifyDoc) -> PrettifyDoc {
+    let command = match doc.as_ref() {
+        Doc::String(_) => return PrettifyDoc::clone(doc),
+        Doc::Children(children) => return clean_children(doc, children),
+        Doc::Command(command) => command,
+    };
+    match command {
+        DocCommand::Group(contents, options) => {
+            if options.expanded_states.is_empty() {
+                if options.id.is_none() && !options.should_break && is_empty_string(contents) {
+                    return string("");
+                }
+                // A group that only wraps an identical group is unwrapped.
+                if let Doc::Command(DocCommand::Group(_, inner_options)) = contents.as_ref() {
+                    if inner_options.expanded_states.is_empty()
+                        && inner_options.id == options.id
+                        && inner_options.should_break == options.should_break
+                    {
+                        return PrettifyDoc::clone(contents);
+                    }
+                }
+            }
+            PrettifyDoc::clone(doc)
+        }
+        DocCommand::Fill(contents, _) if contents.iter().all(is_empty_string) => string(""),
+        DocCommand::IfBreak(break_contents, flat_contents, _)
+            if is_empty_string(break_contents) && is_empty_string(flat_contents) =>
+        {
+            string("")
+        }
+        DocCommand::IndentIfBreak(contents, _, _)
+        | DocCommand::LineSuffix(contents)
+        | DocCommand::Indent(contents)
+        | DocCommand::Align(contents, _)
+            if is_empty_string(contents) =>
+        {
+            string("")
+        }
+        _ => PrettifyDoc::clone(doc),
+    }
+}
+
+// Port of Prettier's cleanDoc. Removes docs that don't affect the output, so the printer has
+// fewer commands to go through: nested concats are flattened, adjacent strings are merged,
+// empty strings are dropped and groups that only wrap an identical group are unwrapped.
+pub fn clean_doc(doc: &PrettifyDoc) -> PrettifyDoc {
+    map_doc(doc, clean)
+}
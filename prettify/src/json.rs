@@ -0,0 +1,322 @@
+use super::doc::{
+    AlignAmount, Doc, DocCommand, DocOptions, DocPointer, GroupId, GroupIdAllocator, LineMode,
+};
+use crate::PrettifyDoc;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+// Docs are encoded the same way Prettier's docs look as JSON, so docs can be shared with
+// tools built around Prettier:
+// - strings are JSON strings and concats are arrays
+// - commands are objects with a "type", e.g. `{ "type": "group", "contents": [], "break": false }`
+// - group ids are numbers
+// - `AlignAmount::Dedent` is `-1` and `AlignAmount::DedentToRoot` is `null`, which is how
+//   Prettier's `-Infinity` is written in JSON
+// - `AlignAmount::MarkAsRoot` is `{ "type": "root" }` like Prettier's `markAsRoot`, while
+//   `AlignAmount::IndentBy` and `AlignAmount::DedentToMark`, which Prettier doesn't have, are
+//   `{ "type": "indent", "width": 2 }` and `{ "type": "dedent-to-mark" }`
+fn doc_to_value(doc: &PrettifyDoc) -> Value {
+    let command = match doc.as_ref() {
+        Doc::String(string) => return Value::String(string.clone()),
+        Doc::Children(children) => return Value::Array(docs_to_value(children)),
+        Doc::Command(command) => command,
+    };
+    let mut object = Map::new();
+    let mut insert = |key: &str, value: Value| {
+        object.insert(key.to_string(), value);
+    };
+    match command {
+        DocCommand::Group(contents, options) => {
+            insert("type", json!("group"));
+            insert("contents", doc_to_value(contents));
+            insert("break", json!(options.should_break));
+            if let Some(id) = options.id {
+                insert("id", json!(id.index()));
+            }
+            if !options.expanded_states.is_empty() {
+                insert(
+                    "expandedStates",
+                    Value::Array(docs_to_value(&options.expanded_states)),
+                );
+            }
+        }
+        DocCommand::Fill(parts, _) => {
+            insert("type", json!("fill"));
+            insert("parts", Value::Array(docs_to_value(parts)));
+        }
+        DocCommand::IfBreak(break_contents, flat_contents, group_id) => {
+            insert("type", json!("if-break"));
+            insert("breakContents", doc_to_value(break_contents));
+            insert("flatContents", doc_to_value(flat_contents));
+            if let Some(id) = group_id {
+                insert("groupId", json!(id.index()));
+            }
+        }
+        DocCommand::IndentIfBreak(contents, group_id, negate) => {
+            insert("type", json!("indent-if-break"));
+            insert("contents", doc_to_value(contents));
+            insert("groupId", json!(group_id.index()));
+            insert("negate", json!(negate));
+        }
+        DocCommand::BreakParent => insert("type", json!("break-parent")),
+        DocCommand::Line(line_mode) => {
+            insert("type", json!("line"));
+            match line_mode {
+                LineMode::Auto => {}
+                LineMode::Soft => insert("soft", json!(true)),
+                LineMode::Hard => insert("hard", json!(true)),
+                LineMode::HardLiteral => {
+                    insert("hard", json!(true));
+                    insert("literal", json!(true));
+                }
+            }
+        }
+        DocCommand::LineSuffix(contents) => {
+            insert("type", json!("line-suffix"));
+            insert("contents", doc_to_value(contents));
+        }
+        DocCommand::LineSuffixBoundary => insert("type", json!("line-suffix-boundary")),
+        DocCommand::Indent(contents) => {
+            insert("type", json!("indent"));
+            insert("contents", doc_to_value(contents));
+        }
+        DocCommand::Align(contents, amount) => {
+            insert("type", json!("align"));
+            insert("contents", doc_to_value(contents));
+            insert(
+                "n",
+                match amount {
+                    AlignAmount::Spaces(spaces) => json!(spaces),
+                    AlignAmount::String(string) => json!(string),
+                    AlignAmount::Dedent => json!(-1),
+                    AlignAmount::DedentToRoot => Value::Null,
+                    AlignAmount::IndentBy(width) => json!({ "type": "indent", "width": width }),
+                    AlignAmount::MarkAsRoot => json!({ "type": "root" }),
+                    AlignAmount::DedentToMark => json!({ "type": "dedent-to-mark" }),
+                },
+            );
+        }
+        DocCommand::Cursor => insert("type", json!("cursor")),
+        DocCommand::Trim => insert("type", json!("trim")),
+        DocCommand::SourceSpan(contents, range) => {
+            insert("type", json!("source-span"));
+            insert("contents", doc_to_value(contents));
+            insert("start", json!(range.start));
+            insert("end", json!(range.end));
+        }
+        DocCommand::Label(label, contents) => {
+            insert("type", json!("label"));
+            insert("label", json!(label));
+            insert("contents", doc_to_value(contents));
+        }
+    }
+    Value::Object(object)
+}
+
+fn docs_to_value(docs: &[PrettifyDoc]) -> Vec<Value> {
+    docs.iter().map(doc_to_value).collect()
+}
+
+pub fn to_json(doc: &PrettifyDoc) -> String {
+    serde_json::to_string_pretty(&doc_to_value(doc)).unwrap()
+}
+
+struct DocReader {
+    group_id_allocator: GroupIdAllocator,
+    // Maps the ids in the JSON to ids from `group_id_allocator`.
+    group_ids: HashMap<String, GroupId>,
+}
+
+impl DocReader {
+    fn group_id(&mut self, value: &Value) -> Result<GroupId, String> {
+        let key = match value {
+            Value::Number(number) => number.to_string(),
+            Value::String(string) => string.clone(),
+            _ => return Err(format!("expected a group id, found {}", value)),
+        };
+        let allocator = &self.group_id_allocator;
+        Ok(*self
+            .group_ids
+            .entry(key)
+            .or_insert_with(|| allocator.allocate()))
+    }
+
+    fn optional_group_id(&mut self, value: Option<&Value>) -> Result<Option<GroupId>, String> {
+        match value {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => self.group_id(value).map(Some),
+        }
+    }
+
+    fn docs(&mut self, value: Option<&Value>) -> Result<Vec<PrettifyDoc>, String> {
+        match value {
+            Some(Value::Array(values)) => values.iter().map(|value| self.doc(value)).collect(),
+            None => Ok(vec![]),
+            Some(value) => Err(format!("expected an array of docs, found {}", value)),
+        }
+    }
+
+    fn contents(&mut self, object: &Map<String, Value>, key: &str) -> Result<PrettifyDoc, String> {
+        match object.get(key) {
+            Some(value) => self.doc(value),
+            None => Err(format!(
+                "missing \"{}\" in {}",
+                key,
+                Value::Object(object.clone())
+            )),
+        }
+    }
+
+    fn doc(&mut self, value: &Value) -> Result<PrettifyDoc, String> {
+        let object = match value {
+            Value::String(string) => return Ok(DocPointer::new(Doc::String(string.clone()))),
+            Value::Array(_) => return Ok(DocPointer::new(Doc::Children(self.docs(Some(value))?))),
+            Value::Object(object) => object,
+            _ => return Err(format!("expected a doc, found {}", value)),
+        };
+        let flag = |key: &str| object.get(key).and_then(Value::as_bool).unwrap_or(false);
+        let command = match object.get("type").and_then(Value::as_str) {
+            Some("group") => {
+                let contents = self.contents(object, "contents")?;
+                DocCommand::Group(
+                    contents,
+                    DocPointer::new(DocOptions {
+                        should_break: flag("break"),
+                        id: self.optional_group_id(object.get("id"))?,
+                        expanded_states: self.docs(object.get("expandedStates"))?,
+                    }),
+                )
+            }
+            Some("fill") => DocCommand::Fill(
+                self.docs(object.get("parts"))?,
+                DocPointer::new(DocOptions::default()),
+            ),
+            Some("if-break") => {
+                let break_contents = self.contents(object, "breakContents")?;
+                let flat_contents = self.contents(object, "flatContents")?;
+                let group_id = self.optional_group_id(object.get("groupId"))?;
+                DocCommand::IfBreak(break_contents, flat_contents, group_id)
+            }
+            Some("indent-if-break") => {
+                let contents = self.contents(object, "contents")?;
+                let group_id = match object.get("groupId") {
+                    Some(value) => self.group_id(value)?,
+                    None => return Err("missing \"groupId\" in indent-if-break".to_string()),
+                };
+                DocCommand::IndentIfBreak(contents, group_id, flag("negate"))
+            }
+            Some("break-parent") => DocCommand::BreakParent,
+            Some("line") => DocCommand::Line(match (flag("hard"), flag("soft"), flag("literal")) {
+                (true, _, true) => LineMode::HardLiteral,
+                (true, _, false) => LineMode::Hard,
+                (false, true, _) => LineMode::Soft,
+                (false, false, _) => LineMode::Auto,
+            }),
+            Some("line-suffix") => DocCommand::LineSuffix(self.contents(object, "contents")?),
+            Some("line-suffix-boundary") => DocCommand::LineSuffixBoundary,
+            Some("indent") => DocCommand::Indent(self.contents(object, "contents")?),
+            Some("align") => {
+                let contents = self.contents(object, "contents")?;
+                let amount = match object.get("n") {
+                    Some(Value::Number(number)) if number.as_i64() == Some(-1) => {
+                        AlignAmount::Dedent
+                    }
+                    Some(Value::Number(number)) if number.is_u64() => {
+                        AlignAmount::Spaces(number.as_u64().unwrap() as usize)
+                    }
+                    Some(Value::String(string)) => AlignAmount::String(string.clone()),
+                    None | Some(Value::Null) => AlignAmount::DedentToRoot,
+                    Some(Value::Object(amount)) => match amount.get("type").and_then(Value::as_str)
+                    {
+                        Some("indent") => match amount.get("width").and_then(Value::as_u64) {
+                            Some(width) => AlignAmount::IndentBy(width as usize),
+                            None => return Err("expected a number \"width\" in indent".to_string()),
+                        },
+                        Some("root") => AlignAmount::MarkAsRoot,
+                        Some("dedent-to-mark") => AlignAmount::DedentToMark,
+                        _ => {
+                            return Err(format!(
+                                "unsupported align amount {}",
+                                Value::Object(amount.clone())
+                            ))
+                        }
+                    },
+                    Some(value) => return Err(format!("unsupported align amount {}", value)),
+                };
+                DocCommand::Align(contents, amount)
+            }
+            Some("cursor") => DocCommand::Cursor,
+            Some("trim") => DocCommand::Trim,
+            Some("label") => {
+                let label = match object.get("label") {
+                    Some(Value::String(label)) => label.clone(),
+                    _ => return Err("expected a string \"label\" in label".to_string()),
+                };
+                DocCommand::Label(label, self.contents(object, "contents")?)
+            }
+            Some("source-span") => {
+                let contents = self.contents(object, "contents")?;
+                let offset = |key: &str| match object.get(key).and_then(Value::as_u64) {
+                    Some(offset) => Ok(offset as usize),
+                    None => Err(format!("expected a number \"{}\" in source-span", key)),
+                };
+                DocCommand::SourceSpan(contents, offset("start")?..offset("end")?)
+            }
+            Some(doc_type) => return Err(format!("unknown doc type \"{}\"", doc_type)),
+            None => return Err(format!("missing \"type\" in {}", value)),
+        };
+        Ok(DocPointer::new(Doc::Command(command)))
+    }
+}
+
+// Docs nest a lot deeper than the data they format, so serde_json's limit of 128 is too low.
+// Reading JSON is recursive though, so deeper JSON is an error instead of a stack overflow,
+// even on the 2 MiB stack of a thread in a debug build.
+const MAX_DEPTH: usize = 256;
+
+// Returns whether the arrays and objects in `json` nest deeper than `MAX_DEPTH`.
+fn exceeds_max_depth(json: &str) -> bool {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for byte in json.bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                if depth > MAX_DEPTH {
+                    return true;
+                }
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    false
+}
+
+// Reads a doc written by `to_json` or by Prettier. Group ids can be numbers or strings.
+pub fn from_json(json: &str) -> Result<PrettifyDoc, String> {
+    if exceeds_max_depth(json) {
+        return Err(format!("docs can't nest deeper than {} levels", MAX_DEPTH));
+    }
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    deserializer.disable_recursion_limit();
+    let value: Value = serde::Deserialize::deserialize(&mut deserializer)
+        .and_then(|value| deserializer.end().map(|_| value))
+        .map_err(|error| error.to_string())?;
+    DocReader {
+        group_id_allocator: GroupIdAllocator::new(),
+        group_ids: HashMap::new(),
+    }
+    .doc(&value)
+}
@@ -1,20 +1,87 @@
-extern crate regex;
+pub use arena::{DocArena, DocId};
 pub use builders::*;
-pub use doc::AlignAmount;
-use doc::{Doc, PrettifyConfig};
-use std::rc::Rc;
+pub use debug::print_doc_to_debug;
+pub use doc::{
+    AlignAmount, CursorOffset, DiagnosedDoc, Doc, DocCommand, DocOptions, DocPointer, EndOfLine,
+    GroupId, GroupIdAllocator, LayoutEngine, LineMode, LineWidthDiagnostic, PrettifyConfig,
+    PrintedDoc, SourceMappedDoc, SourceMapping,
+};
+pub use doc_utils::{clean_doc, map_doc, traverse_doc};
+pub use json::{from_json, to_json};
+pub use print::{
+    get_layout_cost, guess_end_of_line, propagate_breaks, resolve_end_of_line, LayoutCost,
+};
+pub use validate::{validate_doc, DocDiagnostic, DocDiagnosticKind};
 
+mod arena;
 mod builders;
+mod debug;
 mod doc;
+mod doc_utils;
+mod json;
 mod print;
+mod validate;
 
-pub const PRETTIFY_DEFAULT_CONFIG: PrettifyConfig = PrettifyConfig { tab_width: 4 };
-pub type PrettifyDoc<'a> = Rc<Doc<'a>>;
+pub const PRETTIFY_DEFAULT_CONFIG: PrettifyConfig = PrettifyConfig {
+    tab_width: 4,
+    print_width: 80,
+    use_tabs: false,
+    end_of_line: EndOfLine::Lf,
+    layout_engine: LayoutEngine::Greedy,
+};
+pub type PrettifyDoc = DocPointer<Doc>;
 
-pub fn print(doc: Rc<Doc>) -> String {
-    print::print_to_string(doc, &PRETTIFY_DEFAULT_CONFIG)
+pub fn print(doc: PrettifyDoc) -> String {
+    print::print_to_string(doc, "", &PRETTIFY_DEFAULT_CONFIG)
 }
 
-pub fn print_with_config(doc: Rc<Doc>, options: &PrettifyConfig) -> String {
-    print::print_to_string(doc, options)
+pub fn print_with_config(doc: PrettifyDoc, options: &PrettifyConfig) -> String {
+    print::print_to_string(doc, "", options)
+}
+
+// Like `print_with_config`, for a doc formatted from `source`. Strings in a `source_span` that
+// are copies of the source are printed from `source` instead of being copied again, and
+// `EndOfLine::Auto` uses the most common line ending of `source`.
+pub fn print_source_with_config(
+    doc: PrettifyDoc,
+    source: &str,
+    options: &PrettifyConfig,
+) -> String {
+    print::print_to_string(doc, source, options)
+}
+
+pub fn print_with_cursor(doc: PrettifyDoc, options: &PrettifyConfig) -> PrintedDoc {
+    print::print_to_string_with_cursor(doc, options)
+}
+
+// Also returns where the contents of each `source_span` ended up in the output.
+pub fn print_with_source_map(doc: PrettifyDoc, options: &PrettifyConfig) -> SourceMappedDoc {
+    print::print_to_string_with_source_map(doc, options)
+}
+
+// Also returns each line of the output that is still wider than the print width.
+pub fn print_with_diagnostics(doc: PrettifyDoc, options: &PrettifyConfig) -> DiagnosedDoc {
+    print::print_to_string_with_diagnostics(doc, options)
+}
+
+// Streams the printed doc to `writer` one line at a time instead of building the whole string.
+pub fn print_to_writer<W: std::io::Write>(
+    doc: PrettifyDoc,
+    options: &PrettifyConfig,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    print::print_to_writer(doc, options, writer)
+}
+
+pub fn print_arena(arena: &DocArena, root: DocId, options: &PrettifyConfig) -> String {
+    print::print_arena_to_string(arena, root, options)
+}
+
+pub fn print_arena_to_writer<W: std::io::Write>(
+    arena: &DocArena,
+    root: DocId,
+    options: &PrettifyConfig,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    print::print_arena_to_writer(arena, root, options, writer)
 }
@@ -1,5 +1,5 @@
 use super::super::doc::{AlignAmount, PrettifyConfig};
-use super::indent::generate_indent;
+use super::indent::{generate_indent, root_indent};
 use super::shared::{Indent, IndentKind};
 use std::rc::Rc;
 
@@ -8,18 +8,29 @@ use std::rc::Rc;
 // https://sourcegraph.com/github.com/prettier/prettier/-/blob/src/document/doc-printer.js?L23
 pub fn make_align(indent: Rc<Indent>, width: AlignAmount, config: &PrettifyConfig) -> Rc<Indent> {
     let indent_kind = match width {
-        AlignAmount::DedentToRoot => None,
-        AlignAmount::Dedent => Some(IndentKind::Dedent),
-        AlignAmount::Spaces(spaces) => Some(IndentKind::NumberAlign(spaces)),
-        AlignAmount::String(string) => Some(IndentKind::StringAlign(string)),
+        AlignAmount::DedentToRoot => return root_indent(),
+        AlignAmount::DedentToMark => return indent.root.clone().unwrap_or_else(root_indent),
+        AlignAmount::MarkAsRoot => {
+            return Rc::new(Indent {
+                root: Some(Rc::clone(&indent)),
+                ..indent.as_ref().clone()
+            })
+        }
+        AlignAmount::Dedent => IndentKind::Dedent,
+        // Like any other alignment, it only turns into a tab when an indent follows it.
+        AlignAmount::IndentBy(spaces) | AlignAmount::Spaces(spaces) => {
+            IndentKind::NumberAlign(spaces)
+        }
+        AlignAmount::String(string) => IndentKind::StringAlign(string),
     };
     generate_indent(
         indent,
         Indent {
-            kind: indent_kind,
+            kind: Some(indent_kind),
             length: 0,
             queue: Vec::new(),
             value: String::new(),
+            root: None,
         },
         config,
     )
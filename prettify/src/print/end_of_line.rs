@@ -0,0 +1,71 @@
+use super::super::doc::{EndOfLine, PrettifyConfig};
+use std::borrow::Cow;
+
+// The printer doesn't always see the input, so an unresolved `EndOfLine::Auto` falls back to
+// `\n`.
+pub fn get_new_line(end_of_line: EndOfLine) -> &'static str {
+    match end_of_line {
+        EndOfLine::Lf | EndOfLine::Auto => "\n",
+        EndOfLine::Crlf => "\r\n",
+        EndOfLine::Cr => "\r",
+    }
+}
+
+// Replaces every line ending in `text` with `new_line`.
+pub fn convert_end_of_line<'a>(text: &'a str, new_line: &str) -> Cow<'a, str> {
+    if !text.contains(['\n', '\r']) {
+        return Cow::Borrowed(text);
+    }
+    let mut converted = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '\r' if chars.peek() == Some(&'\n') => {
+                chars.next();
+                converted.push_str(new_line);
+            }
+            '\r' | '\n' => converted.push_str(new_line),
+            _ => converted.push(char),
+        }
+    }
+    Cow::Owned(converted)
+}
+
+// Returns the line ending that appears most often in `text`, preferring `\n` on ties and
+// when the text has no line endings at all.
+pub fn guess_end_of_line(text: &str) -> EndOfLine {
+    let mut lf_count = 0;
+    let mut crlf_count = 0;
+    let mut cr_count = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '\r' if chars.peek() == Some(&'\n') => {
+                chars.next();
+                crlf_count += 1;
+            }
+            '\r' => cr_count += 1,
+            '\n' => lf_count += 1,
+            _ => {}
+        }
+    }
+    if crlf_count > lf_count && crlf_count >= cr_count {
+        EndOfLine::Crlf
+    } else if cr_count > lf_count && cr_count > crlf_count {
+        EndOfLine::Cr
+    } else {
+        EndOfLine::Lf
+    }
+}
+
+// Returns `config` with `EndOfLine::Auto` replaced by the line ending guessed from `source`,
+// the text the doc was formatted from.
+pub fn resolve_end_of_line(config: &PrettifyConfig, source: &str) -> PrettifyConfig {
+    match config.end_of_line {
+        EndOfLine::Auto => PrettifyConfig {
+            end_of_line: guess_end_of_line(source),
+            ..*config
+        },
+        _ => *config,
+    }
+}
@@ -1,120 +1,315 @@
-use super::super::doc::{Doc, DocCommand, DocOptions, LineMode, PrettifyConfig};
-use super::align::make_align;
-use super::indent::make_indent;
-use super::shared::{Command, Commands, Mode, Out, OutKind};
-use super::trim::trim;
-use std::borrow::Borrow;
-use std::rc::Rc;
+use super::super::arena::{ArenaDoc, DocArena, DocId};
+use super::super::doc::{LineMode, PrettifyConfig};
+use super::shared::{Command, GroupModeMap, Mode, PrintItem};
+use super::trim::whitespace_width;
+use super::width::get_string_width;
+use std::collections::HashMap;
 
-pub fn fits<'a>(
-    next: &Command<'a>,
-    #[allow(clippy::ptr_arg)] rest_commands: &Commands<'a>,
+// A doc measured on its own from the state it's measured in. Indentation isn't part of the
+// key since measuring stops at the first line break.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct FitsKey {
+    doc: DocId,
+    mode: Mode,
     width: usize,
-    doc_options: &DocOptions<'a>,
-    has_line_suffix_default: bool,
+    trailing_whitespace: usize,
+    has_line_suffix: bool,
     must_be_flat: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Measured {
+    Fits,
+    DoesNotFit,
+    // The doc ended on the same line, leaving this state for what comes after it.
+    Rest {
+        width: usize,
+        trailing_whitespace: usize,
+        has_line_suffix: bool,
+    },
+}
+
+// Measurements of the docs `fits` was called with and of group contents, shared by every call
+// while printing a doc. Conditional groups measure each of their expanded states, which often
+// contain the same docs at the same width.
+pub type FitsCache = HashMap<FitsKey, Measured>;
+
+enum Step {
+    Measure(Mode, PrintItem),
+    // Like `Measure`, but the measurement is cached. Looking up every doc would cost more than
+    // measuring it, so only the doc `fits` was called with and group contents are cached.
+    Cached(Mode, DocId),
+    // The doc that was measured from this state ends here. It can only be cached if it doesn't
+    // read the mode of a group by id, since those are set while printing.
+    End(FitsKey, usize),
+}
+
+#[cfg(test)]
+thread_local! {
+    // The number of steps `fits` took on this thread, so tests can check how it scales.
+    static STEPS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn fits(
+    next: &Command,
+    rest_commands: &[Command],
+    width: usize,
+    has_line_suffix_default: bool,
+    mut must_be_flat: bool,
+    group_mode_map: &GroupModeMap,
+    arena: &DocArena,
     config: &PrettifyConfig,
+    cache: &mut FitsCache,
 ) -> bool {
     let mut remainder = width;
     let mut remainder_index = rest_commands.len();
-    let mut commands: Commands = vec![next.clone()];
-    let mut out: Out = Vec::new();
+    let mut steps: Vec<Step> = vec![match next.2 {
+        PrintItem::Doc(doc) => Step::Cached(next.1, doc),
+        item => Step::Measure(next.1, item),
+    }];
+    // The width of the whitespace a `Trim` would remove.
+    let mut trailing_whitespace = 0;
     let mut has_line_suffix = has_line_suffix_default;
-    while remainder > 0 {
-        if commands.is_empty() {
-            if remainder_index == 0 {
-                return true;
-            }
-            commands.push(rest_commands[remainder_index - 1].clone());
-            remainder_index -= 1;
-            continue;
-        }
-        let (indent, mode, doc) = commands.pop().unwrap();
-
-        match doc.borrow() {
-            Doc::String(string) => {
-                if string.len() > remainder {
-                    return false;
+    // The number of times the mode of a group was read by id.
+    let mut group_mode_reads = 0;
+    // Like Prettier, content that ends exactly at the print width fits, only a line that would
+    // add a column past it doesn't.
+    let result = loop {
+        #[cfg(test)]
+        STEPS.with(|count| count.set(count.get() + 1));
+        let (mode, item) = match steps.pop() {
+            Some(Step::Measure(mode, item)) => (mode, item),
+            Some(Step::Cached(mode, doc)) => {
+                let key = FitsKey {
+                    doc,
+                    mode,
+                    width: remainder,
+                    trailing_whitespace,
+                    has_line_suffix,
+                    must_be_flat,
+                };
+                match cache.get(&key) {
+                    Some(Measured::Fits) => break true,
+                    Some(Measured::DoesNotFit) => break false,
+                    Some(Measured::Rest {
+                        width,
+                        trailing_whitespace: whitespace,
+                        has_line_suffix: line_suffix,
+                    }) => {
+                        remainder = *width;
+                        trailing_whitespace = *whitespace;
+                        has_line_suffix = *line_suffix;
+                        continue;
+                    }
+                    None => {
+                        steps.push(Step::End(key, group_mode_reads));
+                        (mode, PrintItem::Doc(doc))
+                    }
                 }
-                out.push(OutKind::String(string.to_string()));
-                remainder -= string.len();
             }
-            Doc::Children(children) => {
-                for child in children.iter().rev() {
-                    commands.push((Rc::clone(&indent), mode, Rc::clone(child)));
+            Some(Step::End(key, reads)) => {
+                if reads == group_mode_reads {
+                    cache.insert(
+                        key,
+                        Measured::Rest {
+                            width: remainder,
+                            trailing_whitespace,
+                            has_line_suffix,
+                        },
+                    );
                 }
+                continue;
             }
-            Doc::Command(command) => match command {
-                DocCommand::Indent(contents) => {
-                    commands.push((
-                        make_indent(Rc::clone(&indent), config),
-                        mode,
-                        Rc::clone(contents),
-                    ));
-                }
-                DocCommand::Align(contents, width) => {
-                    commands.push((
-                        make_align(indent, width.clone(), config),
-                        mode,
-                        Rc::clone(contents),
-                    ));
+            None if remainder_index == 0 => break true,
+            None => {
+                // Only the docs `fits` was called with must be flat, what comes after them is
+                // measured up to its first line break.
+                must_be_flat = false;
+                let (_, mode, item) = &rest_commands[remainder_index - 1];
+                remainder_index -= 1;
+                steps.push(Step::Measure(*mode, *item));
+                continue;
+            }
+        };
+        match item.resolve(arena) {
+            ArenaDoc::String(span) => {
+                let string = arena.get_text(span);
+                let width = get_string_width(string);
+                if width > remainder {
+                    break false;
                 }
-                DocCommand::Trim => {
-                    remainder += trim(&mut out);
+                remainder -= width;
+                let trimmed = string.trim_end_matches(['\t', ' ']);
+                let whitespace = whitespace_width(&string[trimmed.len()..], config);
+                if trimmed.is_empty() {
+                    trailing_whitespace += whitespace;
+                } else {
+                    trailing_whitespace = whitespace;
                 }
-                DocCommand::Group(contents, options) => {
-                    if must_be_flat && options.should_break {
-                        return false;
-                    }
-                    let group_mode = if options.should_break {
-                        Mode::Break
-                    } else {
-                        Mode::Flat
-                    };
-                    let new_contents =
-                        if !doc_options.expanded_states.is_empty() && group_mode == Mode::Break {
-                            Rc::clone(&options.expanded_states[options.expanded_states.len() - 1])
-                        } else {
-                            Rc::clone(contents)
-                        };
-                    commands.push((indent, group_mode, Rc::clone(&new_contents)));
+            }
+            ArenaDoc::Children(children) | ArenaDoc::Fill(children) => {
+                for child in arena.get_children(children).iter().rev() {
+                    steps.push(Step::Measure(mode, PrintItem::Doc(*child)));
                 }
-                DocCommand::Fill(contents, _) => {
-                    for child in contents.iter().rev() {
-                        commands.push((Rc::clone(&indent), mode, Rc::clone(child)));
-                    }
+            }
+            ArenaDoc::Indent(contents)
+            | ArenaDoc::Align(contents, _)
+            | ArenaDoc::IndentIfBreak(contents, _, _)
+            | ArenaDoc::SourceSpan(contents, _, _) => {
+                steps.push(Step::Measure(mode, PrintItem::Doc(contents)));
+            }
+            ArenaDoc::Trim => {
+                remainder += trailing_whitespace;
+                trailing_whitespace = 0;
+            }
+            ArenaDoc::Group {
+                contents,
+                should_break,
+                expanded_states,
+                ..
+            } => {
+                if must_be_flat && should_break {
+                    break false;
                 }
-                DocCommand::LineSuffix(_) => {
-                    has_line_suffix = true;
+                let group_mode = if should_break {
+                    Mode::Break
+                } else {
+                    Mode::Flat
+                };
+                let new_contents = if !expanded_states.is_empty() && group_mode == Mode::Break {
+                    arena.get_children(expanded_states)[expanded_states.len() - 1]
+                } else {
+                    contents
+                };
+                steps.push(Step::Cached(group_mode, new_contents));
+            }
+            ArenaDoc::LineSuffix(_) => {
+                has_line_suffix = true;
+            }
+            ArenaDoc::LineSuffixBoundary => {
+                if has_line_suffix {
+                    break false;
                 }
-                DocCommand::LineSuffixBoundary => {
-                    if has_line_suffix {
-                        return false;
-                    }
+            }
+            ArenaDoc::Line(line_mode) => match mode {
+                Mode::Break => {
+                    break true;
                 }
-                DocCommand::Line(line_mode) => match mode {
-                    Mode::Break => {
-                        return true;
+                Mode::Flat => match line_mode {
+                    LineMode::Hard | LineMode::HardLiteral => {
+                        break true;
                     }
-                    Mode::Flat => match line_mode {
-                        LineMode::Hard | LineMode::HardLiteral => {
-                            return true;
-                        }
-                        LineMode::Auto => {
-                            out.push(OutKind::String(" ".to_string()));
-                            remainder -= 1;
+                    LineMode::Auto => {
+                        if remainder == 0 {
+                            break false;
                         }
-                        LineMode::Soft => {}
-                    },
+                        remainder -= 1;
+                        trailing_whitespace += 1;
+                    }
+                    LineMode::Soft => {}
                 },
-                DocCommand::BreakParent => {
-                    return false;
-                }
-                DocCommand::IfBreak(_, _, _)
-                | DocCommand::IndentIfBreak(_, _, _)
-                | DocCommand::Cursor => {}
             },
+            ArenaDoc::BreakParent => {
+                break false;
+            }
+            ArenaDoc::IfBreak(break_contents, flat_contents, group_id) => {
+                // Groups that haven't been printed yet are measured as flat, like in Prettier.
+                let group_mode = match group_id {
+                    Some(id) => {
+                        group_mode_reads += 1;
+                        group_mode_map[id.index()].unwrap_or(Mode::Flat)
+                    }
+                    None => mode,
+                };
+                let contents = match group_mode {
+                    Mode::Break => break_contents,
+                    Mode::Flat => flat_contents,
+                };
+                steps.push(Step::Measure(mode, PrintItem::Doc(contents)));
+            }
+            ArenaDoc::Cursor | ArenaDoc::SourceSpanEnd(_) => {}
+        }
+    };
+
+    // Whether the docs that were being measured fit is known as soon as a line break or the
+    // end of the print width is reached.
+    let measured = if result {
+        Measured::Fits
+    } else {
+        Measured::DoesNotFit
+    };
+    for step in steps {
+        if let Step::End(key, reads) = step {
+            if reads == group_mode_reads {
+                cache.insert(key, measured);
+            }
         }
     }
-    false
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::print::print_arena_to_string;
+    use crate::PRETTIFY_DEFAULT_CONFIG;
+
+    // Tries to print the array flat first, like formatters do for the last argument of a
+    // call, so every array is measured once per expanded state, both times from the same
+    // column.
+    fn array(arena: &mut DocArena, items: Vec<DocId>) -> DocId {
+        let open = arena.string("[");
+        let soft_line = arena.soft_line();
+        let comma = arena.string(",");
+        let line = arena.line();
+        let separator = arena.concat(vec![comma, line]);
+        let joined = arena.join(items, separator);
+        let indented = arena.concat(vec![soft_line, joined]);
+        let indented = arena.indent(indented);
+        let close = arena.string("]");
+        let contents = arena.concat(vec![open, indented, soft_line, close]);
+        let break_parent = arena.break_parent();
+        let broken = arena.concat(vec![contents, break_parent]);
+        let expanded = arena.group(broken);
+        arena.conditional_group(vec![contents, expanded], None)
+    }
+
+    // An array of `count` arrays that are nested three deep and too long for a single line.
+    fn nested_arrays(arena: &mut DocArena, count: usize) -> DocId {
+        let items = (0..count)
+            .map(|i| {
+                let number = arena.string(&i.to_string());
+                let words = arena.string("a string that takes up some room");
+                let inner = array(arena, vec![number, words]);
+                let words = arena.string("another string that takes up room");
+                let middle = array(arena, vec![words, inner]);
+                let number = arena.string(&i.to_string());
+                array(arena, vec![number, middle])
+            })
+            .collect();
+        array(arena, items)
+    }
+
+    fn count_steps(count: usize) -> usize {
+        let mut arena = DocArena::new("");
+        let root = nested_arrays(&mut arena, count);
+        STEPS.with(|steps| steps.set(0));
+        print_arena_to_string(&arena, root, &PRETTIFY_DEFAULT_CONFIG);
+        STEPS.with(|steps| steps.get())
+    }
+
+    // Measuring a group must not walk everything after it, or nested conditional groups
+    // become quadratic. Printing ten times the elements should take about ten times the steps.
+    #[test]
+    fn fits_scales_linearly_on_nested_arrays() {
+        let small = count_steps(1_000);
+        let large = count_steps(10_000);
+        assert!(
+            large <= small * 11,
+            "1,000 elements took {} steps, 10,000 elements took {} steps",
+            small,
+            large
+        );
+    }
 }
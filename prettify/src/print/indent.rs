@@ -1,7 +1,18 @@
 use super::super::doc::PrettifyConfig;
 use super::shared::{Indent, IndentKind};
+use super::width::get_string_width;
 use std::rc::Rc;
 
+pub fn root_indent() -> Rc<Indent> {
+    Rc::new(Indent {
+        value: String::new(),
+        length: 0,
+        queue: Vec::new(),
+        kind: None,
+        root: None,
+    })
+}
+
 pub fn make_indent(indent: Rc<Indent>, config: &PrettifyConfig) -> Rc<Indent> {
     generate_indent(
         indent,
@@ -10,44 +21,116 @@ pub fn make_indent(indent: Rc<Indent>, config: &PrettifyConfig) -> Rc<Indent> {
             length: 0,
             queue: Vec::new(),
             value: String::new(),
+            root: None,
         },
         config,
     )
 }
 
+// Mirrors `makeParts` from Prettier. When using tabs, alignments that are followed by an
+// indent are converted to one tab each while trailing alignments stay as spaces, so
+// content lines up no matter what width the reader's editor uses for tabs.
+// https://sourcegraph.com/github.com/prettier/prettier/-/blob/src/document/doc-printer.js
 pub fn generate_indent(
     indent: Rc<Indent>,
     new_indent: Indent,
     config: &PrettifyConfig,
 ) -> Rc<Indent> {
     let queue = generate_initial_queue(&indent, Rc::new(new_indent));
-    let mut value = String::new();
+    let mut parts = IndentParts::new(config);
 
     for item in &queue {
         match &item.kind {
             Some(IndentKind::Indent) => {
-                value.push_str(&" ".repeat(config.tab_width));
+                parts.flush();
+                if config.use_tabs {
+                    parts.add_tabs(1);
+                } else {
+                    parts.add_spaces(config.tab_width);
+                }
             }
             Some(IndentKind::StringAlign(text)) => {
-                value.push_str(text);
+                parts.flush();
+                parts.value.push_str(text);
+                parts.length += get_string_width(text);
             }
             Some(IndentKind::NumberAlign(width)) => {
-                value.push_str(&" ".repeat(*width));
+                parts.last_tabs += 1;
+                parts.last_spaces += width;
             }
             Some(IndentKind::Dedent) | None => {
                 panic!("Unexpected indent kind");
             }
         }
     }
+    parts.flush_spaces();
 
     Rc::new(Indent {
-        length: value.len(),
-        value,
+        length: parts.length,
+        value: parts.value,
         queue,
         kind: indent.as_ref().clone().kind,
+        root: indent.root.clone(),
     })
 }
 
+struct IndentParts<'a> {
+    value: String,
+    length: usize,
+    last_tabs: usize,
+    last_spaces: usize,
+    config: &'a PrettifyConfig,
+}
+
+impl<'a> IndentParts<'a> {
+    fn new(config: &'a PrettifyConfig) -> Self {
+        IndentParts {
+            value: String::new(),
+            length: 0,
+            last_tabs: 0,
+            last_spaces: 0,
+            config,
+        }
+    }
+
+    fn add_tabs(&mut self, count: usize) {
+        self.value.push_str(&"\t".repeat(count));
+        self.length += self.config.tab_width * count;
+    }
+
+    fn add_spaces(&mut self, count: usize) {
+        self.value.push_str(&" ".repeat(count));
+        self.length += count;
+    }
+
+    fn flush(&mut self) {
+        if self.config.use_tabs {
+            self.flush_tabs();
+        } else {
+            self.flush_spaces();
+        }
+    }
+
+    fn flush_tabs(&mut self) {
+        if self.last_tabs > 0 {
+            self.add_tabs(self.last_tabs);
+        }
+        self.reset_last();
+    }
+
+    fn flush_spaces(&mut self) {
+        if self.last_spaces > 0 {
+            self.add_spaces(self.last_spaces);
+        }
+        self.reset_last();
+    }
+
+    fn reset_last(&mut self) {
+        self.last_tabs = 0;
+        self.last_spaces = 0;
+    }
+}
+
 fn generate_initial_queue(indent: &Indent, new_indent: Rc<Indent>) -> Vec<Rc<Indent>> {
     match new_indent.kind {
         Some(IndentKind::Dedent) => {
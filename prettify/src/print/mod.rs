@@ -1,8 +1,19 @@
 mod align;
+mod end_of_line;
 mod fits;
 mod indent;
+mod optimal;
 mod print_to_string;
+mod propagate_breaks;
 mod shared;
 mod trim;
+mod width;
 
-pub use print_to_string::print_to_string;
+pub use end_of_line::{guess_end_of_line, resolve_end_of_line};
+pub use optimal::{get_layout_cost, LayoutCost};
+pub use print_to_string::{
+    print_arena_to_string, print_arena_to_writer, print_to_string, print_to_string_with_cursor,
+    print_to_string_with_diagnostics, print_to_string_with_source_map, print_to_writer,
+};
+pub(crate) use propagate_breaks::group_breaks;
+pub use propagate_breaks::propagate_breaks;
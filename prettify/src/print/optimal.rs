@@ -0,0 +1,779 @@
+use super::super::doc::{
+    AlignAmount, Doc, DocCommand, DocOptions, DocPointer, GroupId, LineMode, PrettifyConfig,
+};
+use super::align::make_align;
+use super::indent::{make_indent, root_indent};
+use super::shared::{Indent, Mode};
+use super::width::get_string_width;
+use crate::{
+    align, concat, group_with_options, indent, label, line_suffix, source_span, string,
+    traverse_doc, PrettifyDoc,
+};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+// The cost of a layout. Layouts are compared by how many columns they overflow the print width
+// by first, then by how many lines they take up and then by how deeply those lines are
+// indented.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Default)]
+pub struct LayoutCost {
+    pub overflow: usize,
+    pub lines: usize,
+    pub nesting: usize,
+}
+
+// Measures the cost of printed text the same way the optimal engine measures its layouts, so
+// the output of both engines can be compared.
+pub fn get_layout_cost(formatted: &str, config: &PrettifyConfig) -> LayoutCost {
+    let mut cost = LayoutCost::default();
+    for (index, line) in formatted.lines().enumerate() {
+        let content = line.trim_start_matches('\t');
+        let tabs = line.len() - content.len();
+        let indentation = tabs * config.tab_width + get_string_width(content)
+            - get_string_width(content.trim_start());
+        let width = tabs * config.tab_width + get_string_width(content);
+        cost.overflow += width.saturating_sub(config.print_width);
+        if index > 0 {
+            cost.lines += 1;
+            cost.nesting += indentation;
+        }
+    }
+    cost
+}
+
+// One way to print a doc: the column it ends at, its cost and the doc with every choice made.
+#[derive(Clone)]
+struct Layout {
+    column: usize,
+    cost: LayoutCost,
+    doc: PrettifyDoc,
+}
+
+// The docs of the first children of a concat or fill, last first. Partial layouts that were
+// built from the same partial layout share its parts instead of copying them.
+struct Parts {
+    doc: PrettifyDoc,
+    previous: Option<Rc<Parts>>,
+}
+
+impl Drop for Parts {
+    // Drops long lists one part at a time instead of recursively.
+    fn drop(&mut self) {
+        let mut previous = self.previous.take();
+        while let Some(parts) = previous {
+            previous = match Rc::try_unwrap(parts) {
+                Ok(mut parts) => parts.previous.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+// A layout of the first children of a concat or fill.
+struct PartialLayout {
+    column: usize,
+    cost: LayoutCost,
+    parts: Option<Rc<Parts>>,
+}
+
+impl PartialLayout {
+    fn into_layout(self) -> Layout {
+        let mut docs = Vec::new();
+        let mut parts = self.parts.as_deref();
+        while let Some(part) = parts {
+            docs.push(PrettifyDoc::clone(&part.doc));
+            parts = part.previous.as_deref();
+        }
+        docs.reverse();
+        Layout {
+            column: self.column,
+            cost: self.cost,
+            doc: concat(docs),
+        }
+    }
+}
+
+// The mode that was chosen for a group around a doc and the modes of the groups around it.
+struct GroupMode {
+    id: GroupId,
+    mode: Mode,
+    outer: Option<Rc<GroupMode>>,
+}
+
+// The modes that were chosen for the groups around a doc, innermost first. Equal lists share
+// an interned id, which layouts are memoized by.
+#[derive(Clone)]
+struct GroupModes {
+    id: usize,
+    innermost: Option<Rc<GroupMode>>,
+}
+
+// An indentation and its id, which layouts are memoized by. Indentations that are made the same
+// way from the same indentation share an id.
+#[derive(Clone)]
+struct Indentation {
+    indent: Rc<Indent>,
+    id: usize,
+}
+
+// (doc, column, indentation id, mode, group modes id)
+type LayoutKey = (*const Doc, usize, usize, Mode, usize);
+
+// The layouts of `doc` printed from `column`.
+struct Request {
+    doc: PrettifyDoc,
+    column: usize,
+    indentation: Indentation,
+    mode: Mode,
+    group_modes: GroupModes,
+}
+
+impl Request {
+    fn key(&self) -> LayoutKey {
+        (
+            DocPointer::as_ptr(&self.doc),
+            self.column,
+            self.indentation.id,
+            self.mode,
+            self.group_modes.id,
+        )
+    }
+
+    // The layouts of `doc`, printed from the same state.
+    fn child(&self, doc: &PrettifyDoc) -> Request {
+        Request {
+            doc: PrettifyDoc::clone(doc),
+            column: self.column,
+            indentation: self.indentation.clone(),
+            mode: self.mode,
+            group_modes: self.group_modes.clone(),
+        }
+    }
+}
+
+// What to do with the layouts of a request once they are found. These are kept on a stack
+// instead of recursing, so deeply nested docs don't overflow the stack.
+enum Frame {
+    Memoize(LayoutKey),
+    // Maps the docs of the layouts without changing where they end or what they cost.
+    Wrap(Box<dyn Fn(PrettifyDoc) -> PrettifyDoc>),
+    // Line suffixes are printed at the end of the line, so they don't move the column.
+    LineSuffix(usize),
+    Sequence(Sequence),
+    Group(GroupSearch),
+    // The layouts were found from the start of a line for a flat doc. They're moved to where the
+    // request starts, unless they contain a line break.
+    Move(Request),
+}
+
+enum Step {
+    Done(Rc<Vec<Layout>>),
+    // Finds the layouts of the request and passes them to the frame, or returns them as they
+    // are without one.
+    Request(Option<Frame>, Request),
+}
+
+// Keeps the layouts that are cheaper than every layout that ends at an earlier column. What
+// follows a layout can only cost more when it starts at a later column, so the others can
+// never be part of the cheapest layout.
+fn keep_cheapest<T, F: Fn(&T) -> (usize, LayoutCost)>(mut layouts: Vec<T>, measure: F) -> Vec<T> {
+    layouts.sort_by_key(|layout| measure(layout));
+    let mut cheapest: Option<LayoutCost> = None;
+    layouts.retain(|layout| {
+        let (_, cost) = measure(layout);
+        if cheapest.is_some_and(|cheapest| cheapest <= cost) {
+            return false;
+        }
+        cheapest = Some(cost);
+        true
+    });
+    layouts
+}
+
+fn add_costs(first: LayoutCost, second: LayoutCost) -> LayoutCost {
+    LayoutCost {
+        overflow: first.overflow + second.overflow,
+        lines: first.lines + second.lines,
+        nesting: first.nesting + second.nesting,
+    }
+}
+
+fn get_group_mode(
+    group_modes: &GroupModes,
+    broken_groups: &HashSet<GroupId>,
+    id: Option<GroupId>,
+    mode: Mode,
+) -> Mode {
+    let Some(id) = id else {
+        return mode;
+    };
+    let mut group_mode = group_modes.innermost.as_deref();
+    while let Some(outer) = group_mode {
+        if outer.id == id {
+            return outer.mode;
+        }
+        group_mode = outer.outer.as_deref();
+    }
+    if broken_groups.contains(&id) {
+        return Mode::Break;
+    }
+    mode
+}
+
+fn wrap(layouts: &[Layout], wrap: &dyn Fn(PrettifyDoc) -> PrettifyDoc) -> Vec<Layout> {
+    layouts
+        .iter()
+        .map(|layout| Layout {
+            doc: wrap(PrettifyDoc::clone(&layout.doc)),
+            ..layout.clone()
+        })
+        .collect()
+}
+
+// The parts of a concat or fill.
+fn get_parts(doc: &PrettifyDoc) -> &[PrettifyDoc] {
+    match doc.as_ref() {
+        Doc::Children(parts) | Doc::Command(DocCommand::Fill(parts, _)) => parts,
+        _ => &[],
+    }
+}
+
+// Finds the layouts of the parts of a concat or fill printed one after the other, one part at a
+// time. Each part is printed in each of `modes`.
+struct Sequence {
+    doc: PrettifyDoc,
+    modes: Vec<Mode>,
+    indentation: Indentation,
+    group_modes: GroupModes,
+    // The part whose layouts are being added to the partial layouts.
+    index: usize,
+    partial_layouts: Vec<PartialLayout>,
+    // How many pairs of partial layout and mode the layouts of the part were added after.
+    added: usize,
+    next: Vec<PartialLayout>,
+}
+
+impl Sequence {
+    fn next_step(self) -> Step {
+        let parts = get_parts(&self.doc);
+        if self.index == parts.len() {
+            return Step::Done(Rc::new(
+                self.partial_layouts
+                    .into_iter()
+                    .map(PartialLayout::into_layout)
+                    .collect(),
+            ));
+        }
+        let request = Request {
+            doc: PrettifyDoc::clone(&parts[self.index]),
+            column: self.partial_layouts[self.added / self.modes.len()].column,
+            indentation: self.indentation.clone(),
+            mode: self.modes[self.added % self.modes.len()],
+            group_modes: self.group_modes.clone(),
+        };
+        Step::Request(Some(Frame::Sequence(self)), request)
+    }
+
+    fn add_layouts(mut self, layouts: &[Layout]) -> Step {
+        let partial_layout = &self.partial_layouts[self.added / self.modes.len()];
+        for layout in layouts {
+            self.next.push(PartialLayout {
+                column: layout.column,
+                cost: add_costs(partial_layout.cost, layout.cost),
+                parts: Some(Rc::new(Parts {
+                    doc: PrettifyDoc::clone(&layout.doc),
+                    previous: partial_layout.parts.clone(),
+                })),
+            });
+        }
+        self.added += 1;
+        if self.added == self.partial_layouts.len() * self.modes.len() {
+            self.partial_layouts = keep_cheapest(std::mem::take(&mut self.next), |layout| {
+                (layout.column, layout.cost)
+            });
+            self.added = 0;
+            self.index += 1;
+        }
+        self.next_step()
+    }
+}
+
+enum GroupStage {
+    Flat,
+    ExpandedState(usize),
+    Broken,
+}
+
+// Finds the layouts of a group that isn't printed flat by the group around it: flat, or the first
+// expanded state that fits, and broken.
+struct GroupSearch {
+    request: Request,
+    contents: PrettifyDoc,
+    id: Option<GroupId>,
+    expanded_states: Vec<PrettifyDoc>,
+    stage: GroupStage,
+    layouts: Vec<Layout>,
+}
+
+// Finds the cheapest layout of a doc in the style of Wadler's and Bernardy's pretty printers:
+// the layouts of each doc are built from the layouts of its children, keeping only the ones
+// that can still be part of the cheapest layout.
+struct LayoutSearch<'a> {
+    config: &'a PrettifyConfig,
+    layouts: HashMap<LayoutKey, Rc<Vec<Layout>>>,
+    // The indentations made from another indentation by an indent, or by an alignment.
+    indentations: HashMap<(usize, Option<AlignAmount>), Indentation>,
+    // (outer group modes id, group id, mode) for every list of group modes but the empty one.
+    group_modes: HashMap<(usize, GroupId, Mode), usize>,
+    root_indentation: Indentation,
+    // The flat docs that contain a line break, by the group modes they were printed with.
+    multi_line: HashSet<(*const Doc, usize)>,
+    // The ids of the groups that break wherever they're printed.
+    broken_groups: HashSet<GroupId>,
+}
+
+impl LayoutSearch<'_> {
+    fn text(&self, column: usize, text: &str, doc: PrettifyDoc) -> Layout {
+        self.single_line(column, get_string_width(text), doc)
+    }
+
+    // A layout that is `length` columns wide and doesn't break.
+    fn single_line(&self, column: usize, length: usize, doc: PrettifyDoc) -> Layout {
+        let end = column + length;
+        let width = self.config.print_width;
+        Layout {
+            column: end,
+            cost: LayoutCost {
+                overflow: end.saturating_sub(width) - column.saturating_sub(width),
+                ..LayoutCost::default()
+            },
+            doc,
+        }
+    }
+
+    fn new_line(&self, indentation: &Indent, literal: bool, doc: &PrettifyDoc) -> Layout {
+        let column = match (literal, &indentation.root) {
+            (true, Some(root)) => root.length,
+            (true, None) => 0,
+            (false, _) => indentation.length,
+        };
+        Layout {
+            column,
+            cost: LayoutCost {
+                overflow: 0,
+                lines: 1,
+                nesting: column,
+            },
+            doc: PrettifyDoc::clone(doc),
+        }
+    }
+
+    fn make_indentation(
+        &mut self,
+        indentation: &Indentation,
+        amount: Option<&AlignAmount>,
+    ) -> Indentation {
+        let key = (indentation.id, amount.cloned());
+        if let Some(made) = self.indentations.get(&key) {
+            return made.clone();
+        }
+        let indent = Rc::clone(&indentation.indent);
+        let made = Indentation {
+            indent: match amount {
+                Some(amount) => make_align(indent, amount.clone(), self.config),
+                None => make_indent(indent, self.config),
+            },
+            id: self.indentations.len() + 1,
+        };
+        self.indentations.insert(key, made.clone());
+        made
+    }
+
+    fn with_group_mode(
+        &mut self,
+        group_modes: &GroupModes,
+        id: Option<GroupId>,
+        mode: Mode,
+    ) -> GroupModes {
+        let Some(id) = id else {
+            return group_modes.clone();
+        };
+        let count = self.group_modes.len();
+        GroupModes {
+            id: *self
+                .group_modes
+                .entry((group_modes.id, id, mode))
+                .or_insert(count + 1),
+            innermost: Some(Rc::new(GroupMode {
+                id,
+                mode,
+                outer: group_modes.innermost.clone(),
+            })),
+        }
+    }
+
+    fn get_layouts(&mut self, request: Request) -> Rc<Vec<Layout>> {
+        let mut frames: Vec<Frame> = Vec::new();
+        let mut step = Step::Request(None, request);
+        loop {
+            let layouts = match step {
+                Step::Done(layouts) => layouts,
+                Step::Request(frame, mut request) => {
+                    frames.extend(frame);
+                    // A flat doc without line breaks is laid out the same way from any column and
+                    // indentation, so it's only laid out from the start of a line.
+                    let is_start = request.column == 0 && request.indentation.id == 0;
+                    if request.mode == Mode::Flat
+                        && !is_start
+                        && !self
+                            .multi_line
+                            .contains(&(DocPointer::as_ptr(&request.doc), request.group_modes.id))
+                    {
+                        let start = Request {
+                            column: 0,
+                            indentation: self.root_indentation.clone(),
+                            ..request.child(&request.doc)
+                        };
+                        frames.push(Frame::Move(request));
+                        request = start;
+                    }
+                    let key = request.key();
+                    match self.layouts.get(&key) {
+                        Some(layouts) => Rc::clone(layouts),
+                        None => {
+                            frames.push(Frame::Memoize(key));
+                            step = self.find_layouts(request);
+                            continue;
+                        }
+                    }
+                }
+            };
+            step = match frames.pop() {
+                None => return layouts,
+                Some(Frame::Memoize(key)) => {
+                    self.layouts.insert(key, Rc::clone(&layouts));
+                    Step::Done(layouts)
+                }
+                Some(Frame::Wrap(wrapper)) => Step::Done(Rc::new(wrap(&layouts, &wrapper))),
+                Some(Frame::LineSuffix(column)) => {
+                    let cheapest = layouts.iter().min_by_key(|layout| layout.cost).unwrap();
+                    Step::Done(Rc::new(vec![Layout {
+                        column,
+                        cost: LayoutCost::default(),
+                        doc: line_suffix(PrettifyDoc::clone(&cheapest.doc)),
+                    }]))
+                }
+                Some(Frame::Sequence(sequence)) => sequence.add_layouts(&layouts),
+                Some(Frame::Group(group)) => self.add_group_layouts(group, layouts),
+                Some(Frame::Move(request)) => {
+                    if layouts.iter().all(|layout| layout.cost.lines == 0) {
+                        let column = request.column;
+                        Step::Done(Rc::new(
+                            layouts
+                                .iter()
+                                .map(|layout| {
+                                    self.single_line(
+                                        column,
+                                        layout.column,
+                                        PrettifyDoc::clone(&layout.doc),
+                                    )
+                                })
+                                .collect(),
+                        ))
+                    } else {
+                        self.multi_line
+                            .insert((DocPointer::as_ptr(&request.doc), request.group_modes.id));
+                        Step::Request(None, request)
+                    }
+                }
+            };
+        }
+    }
+
+    fn sequence(&self, request: Request, modes: Vec<Mode>) -> Step {
+        Sequence {
+            partial_layouts: vec![PartialLayout {
+                column: request.column,
+                cost: LayoutCost::default(),
+                parts: None,
+            }],
+            doc: request.doc,
+            modes,
+            indentation: request.indentation,
+            group_modes: request.group_modes,
+            index: 0,
+            added: 0,
+            next: Vec::new(),
+        }
+        .next_step()
+    }
+
+    fn find_layouts(&mut self, request: Request) -> Step {
+        let done = |layout: Layout| Step::Done(Rc::new(vec![layout]));
+        let command = match request.doc.as_ref() {
+            Doc::String(text) => {
+                return done(self.text(request.column, text, PrettifyDoc::clone(&request.doc)))
+            }
+            Doc::Children(_) => {
+                let mode = request.mode;
+                return self.sequence(request, vec![mode]);
+            }
+            Doc::Command(command) => command,
+        };
+        match command {
+            DocCommand::Group(contents, options) => {
+                // Like the greedy engine, a group that must break breaks even inside of a flat
+                // group.
+                if request.mode == Mode::Flat && !options.should_break {
+                    let group_modes =
+                        self.with_group_mode(&request.group_modes, options.id, Mode::Flat);
+                    return Step::Request(
+                        None,
+                        Request {
+                            group_modes,
+                            ..request.child(contents)
+                        },
+                    );
+                }
+                let group = GroupSearch {
+                    contents: PrettifyDoc::clone(contents),
+                    id: options.id,
+                    expanded_states: options.expanded_states.clone(),
+                    stage: GroupStage::Flat,
+                    layouts: Vec::new(),
+                    request: request.child(&request.doc),
+                };
+                self.start_group(group, options.should_break)
+            }
+            // Each part of a fill is printed flat or broken on its own.
+            DocCommand::Fill(_, _) => {
+                let modes = match request.mode {
+                    Mode::Break => vec![Mode::Flat, Mode::Break],
+                    Mode::Flat => vec![Mode::Flat],
+                };
+                self.sequence(request.child(&request.doc), modes)
+            }
+            // Groups that aren't around the `if_break` are assumed to have the current mode,
+            // unless they must break.
+            DocCommand::IfBreak(break_contents, flat_contents, id) => {
+                let contents = match get_group_mode(
+                    &request.group_modes,
+                    &self.broken_groups,
+                    *id,
+                    request.mode,
+                ) {
+                    Mode::Break => break_contents,
+                    Mode::Flat => flat_contents,
+                };
+                Step::Request(None, request.child(contents))
+            }
+            DocCommand::IndentIfBreak(contents, id, negate) => {
+                let should_indent = match get_group_mode(
+                    &request.group_modes,
+                    &self.broken_groups,
+                    Some(*id),
+                    request.mode,
+                ) {
+                    Mode::Break => !negate,
+                    Mode::Flat => *negate,
+                };
+                if should_indent {
+                    self.indent(&request, contents)
+                } else {
+                    Step::Request(None, request.child(contents))
+                }
+            }
+            DocCommand::Line(line_mode) => match (request.mode, line_mode) {
+                (Mode::Flat, LineMode::Auto) => done(self.text(request.column, " ", string(" "))),
+                (Mode::Flat, LineMode::Soft) => done(self.text(request.column, "", string(""))),
+                (_, line_mode) => done(self.new_line(
+                    &request.indentation.indent,
+                    *line_mode == LineMode::HardLiteral,
+                    &request.doc,
+                )),
+            },
+            DocCommand::Indent(contents) => self.indent(&request, contents),
+            DocCommand::Align(contents, amount) => {
+                let indentation = self.make_indentation(&request.indentation, Some(amount));
+                let amount = amount.clone();
+                Step::Request(
+                    Some(Frame::Wrap(Box::new(move |doc| align(doc, amount.clone())))),
+                    Request {
+                        indentation,
+                        ..request.child(contents)
+                    },
+                )
+            }
+            DocCommand::LineSuffix(contents) => Step::Request(
+                Some(Frame::LineSuffix(request.column)),
+                request.child(contents),
+            ),
+            DocCommand::Label(name, contents) => {
+                let name = name.clone();
+                Step::Request(
+                    Some(Frame::Wrap(Box::new(move |doc| label(name.clone(), doc)))),
+                    request.child(contents),
+                )
+            }
+            DocCommand::SourceSpan(contents, range) => {
+                let range = range.clone();
+                Step::Request(
+                    Some(Frame::Wrap(Box::new(move |doc| {
+                        source_span(doc, range.clone())
+                    }))),
+                    request.child(contents),
+                )
+            }
+            DocCommand::BreakParent
+            | DocCommand::LineSuffixBoundary
+            | DocCommand::Cursor
+            | DocCommand::Trim => done(Layout {
+                column: request.column,
+                cost: LayoutCost::default(),
+                doc: PrettifyDoc::clone(&request.doc),
+            }),
+        }
+    }
+
+    fn indent(&mut self, request: &Request, contents: &PrettifyDoc) -> Step {
+        let indentation = self.make_indentation(&request.indentation, None);
+        Step::Request(
+            Some(Frame::Wrap(Box::new(indent))),
+            Request {
+                indentation,
+                ..request.child(contents)
+            },
+        )
+    }
+
+    fn start_group(&mut self, mut group: GroupSearch, should_break: bool) -> Step {
+        let flat_modes = self.with_group_mode(&group.request.group_modes, group.id, Mode::Flat);
+        let doc = match (should_break, group.expanded_states.first()) {
+            (false, None) => {
+                group.stage = GroupStage::Flat;
+                PrettifyDoc::clone(&group.contents)
+            }
+            // The states of a conditional group are listed from most to least preferred, so the
+            // first one that fits is used, like in the greedy engine.
+            (false, Some(state)) => {
+                group.stage = GroupStage::ExpandedState(0);
+                PrettifyDoc::clone(state)
+            }
+            (true, _) => return self.break_group(group),
+        };
+        let request = Request {
+            mode: Mode::Flat,
+            group_modes: flat_modes,
+            ..group.request.child(&doc)
+        };
+        Step::Request(Some(Frame::Group(group)), request)
+    }
+
+    fn break_group(&mut self, mut group: GroupSearch) -> Step {
+        let break_modes = self.with_group_mode(&group.request.group_modes, group.id, Mode::Break);
+        let broken = group.expanded_states.last().unwrap_or(&group.contents);
+        let request = Request {
+            mode: Mode::Break,
+            group_modes: break_modes,
+            ..group.request.child(broken)
+        };
+        group.stage = GroupStage::Broken;
+        Step::Request(Some(Frame::Group(group)), request)
+    }
+
+    fn add_group_layouts(&mut self, mut group: GroupSearch, layouts: Rc<Vec<Layout>>) -> Step {
+        match group.stage {
+            GroupStage::Flat => {
+                group.layouts.extend(layouts.iter().cloned());
+                self.break_group(group)
+            }
+            GroupStage::ExpandedState(index) => {
+                if layouts.iter().any(|layout| layout.cost.overflow == 0) {
+                    return Step::Done(layouts);
+                }
+                match group.expanded_states.get(index + 1) {
+                    Some(state) => {
+                        let request = Request {
+                            mode: Mode::Flat,
+                            group_modes: self.with_group_mode(
+                                &group.request.group_modes,
+                                group.id,
+                                Mode::Flat,
+                            ),
+                            ..group.request.child(state)
+                        };
+                        group.stage = GroupStage::ExpandedState(index + 1);
+                        Step::Request(Some(Frame::Group(group)), request)
+                    }
+                    None => self.break_group(group),
+                }
+            }
+            // Broken groups keep their id, so the greedy printer that prints the chosen layout
+            // agrees on their mode.
+            GroupStage::Broken => {
+                let id = group.id;
+                group.layouts.extend(wrap(&layouts, &|doc| {
+                    group_with_options(
+                        doc,
+                        DocOptions {
+                            should_break: true,
+                            id,
+                            ..DocOptions::default()
+                        },
+                    )
+                }));
+                Step::Done(Rc::new(keep_cheapest(group.layouts, |layout| {
+                    (layout.column, layout.cost)
+                })))
+            }
+        }
+    }
+}
+
+fn get_broken_groups(doc: &PrettifyDoc) -> HashSet<GroupId> {
+    let mut broken_groups = HashSet::new();
+    traverse_doc(
+        doc,
+        |doc| {
+            if let Doc::Command(DocCommand::Group(_, options)) = doc.as_ref() {
+                if let (true, Some(id)) = (options.should_break, options.id) {
+                    broken_groups.insert(id);
+                }
+            }
+            true
+        },
+        |_| {},
+    );
+    broken_groups
+}
+
+// Returns `doc` with the cheapest choice made for every group, conditional group and fill, so
+// the greedy printer prints the cheapest layout. `doc` must have its breaks propagated.
+pub fn choose_cheapest_layout(doc: &PrettifyDoc, config: &PrettifyConfig) -> PrettifyDoc {
+    let root_indentation = Indentation {
+        indent: root_indent(),
+        id: 0,
+    };
+    let mut search = LayoutSearch {
+        config,
+        layouts: HashMap::new(),
+        indentations: HashMap::new(),
+        group_modes: HashMap::new(),
+        root_indentation: root_indentation.clone(),
+        multi_line: HashSet::new(),
+        broken_groups: get_broken_groups(doc),
+    };
+    let request = Request {
+        doc: PrettifyDoc::clone(doc),
+        column: 0,
+        indentation: root_indentation,
+        mode: Mode::Break,
+        group_modes: GroupModes {
+            id: 0,
+            innermost: None,
+        },
+    };
+    let layouts = search.get_layouts(request);
+    let cheapest = layouts.iter().min_by_key(|layout| layout.cost).unwrap();
+    PrettifyDoc::clone(&cheapest.doc)
+}
@@ -1,320 +1,539 @@
-use super::super::doc::{Doc, DocCommand, LineMode, PrettifyConfig};
-use super::align::make_align;
-use super::fits::fits;
-use super::indent::make_indent;
-use super::shared::{
-    Command, Commands, GroupModeMap, Indent, LineSuffixes, Mode, Out, OutKind, NEW_LINE,
-    PRINT_WIDTH,
+use super::super::arena::{ArenaDoc, DocArena, DocId};
+use super::super::doc::{
+    CursorOffset, DiagnosedDoc, LayoutEngine, LineMode, LineWidthDiagnostic, PrettifyConfig,
+    PrintedDoc, SourceMappedDoc, SourceMapping,
 };
+use super::align::make_align;
+use super::end_of_line::{convert_end_of_line, get_new_line, resolve_end_of_line};
+use super::fits::{fits, FitsCache};
+use super::indent::{make_indent, root_indent};
+use super::optimal::choose_cheapest_layout;
+use super::propagate_breaks::propagate_breaks;
+use super::shared::{Command, Commands, GroupModeMap, LineSuffixes, Mode, Out, PrintItem};
 use super::trim::trim;
-use crate::indent as build_indent;
+use super::width::get_string_width;
+use crate::doc_utils::clean_doc;
+#[cfg(all(debug_assertions, feature = "validate"))]
+use crate::validate::validate_doc;
+use crate::PrettifyDoc;
 
-use std::borrow::Borrow;
-use std::collections::HashMap;
+use std::io::{self, Write};
 use std::rc::Rc;
 
-fn root_indent() -> Rc<Indent> {
-    Rc::new(Indent {
-        value: String::new(),
-        length: 0,
-        queue: Vec::new(),
-        kind: None,
-    })
+// Reports the line that just ended if it's wider than the print width, and starts the next one.
+fn end_line(
+    diagnostics: Option<&mut Vec<LineWidthDiagnostic>>,
+    overflow: &mut Option<LineWidthDiagnostic>,
+    line: &mut usize,
+    width: usize,
+    config: &PrettifyConfig,
+) {
+    if let (Some(diagnostics), Some(mut diagnostic)) = (diagnostics, overflow.take()) {
+        if width > config.print_width {
+            diagnostic.width = width;
+            diagnostics.push(diagnostic);
+        }
+    }
+    *line += 1;
 }
 
 // This function is long for three reasons:
 // 1. Using a stack is substantially faster than making it recursive
-// 2. Breaking each match block into separate functions requires the use of Cow<'a, Doc<'a>
-//    which creates a lot of extra .clone() calls.
-// 3. Using Cows leads to a lot of extra boilerplate and unintuitive patterns.
-pub fn print_to_string<'a>(doc: Rc<Doc<'a>>, config: &PrettifyConfig) -> String {
+// 2. Breaking each match block into separate functions requires passing most of the
+//    printer's state to each of them.
+// 3. Doing so leads to a lot of extra boilerplate and unintuitive patterns.
+// Everything before the last line break is passed to `emit` as soon as it's printed, along
+// with the byte offsets of the cursors in it, since `Trim` only removes whitespace up to the
+// last line break.
+// Source spans are only tracked when `mappings` is given, and lines that are too wide are only
+// reported when `diagnostics` is given.
+fn print_to_out<F: FnMut(&str, &[usize]) -> io::Result<()>>(
+    arena: &DocArena,
+    root: DocId,
+    config: &PrettifyConfig,
+    mut mappings: Option<&mut Vec<SourceMapping>>,
+    mut diagnostics: Option<&mut Vec<LineWidthDiagnostic>>,
+    mut emit: F,
+) -> io::Result<()> {
     let mut pos: usize = 0;
+    let mut line: usize = 0;
+    // The string that pushed the current line past the print width.
+    let mut overflow: Option<LineWidthDiagnostic> = None;
+    // The number of bytes that were already passed to `emit`.
+    let mut emitted: usize = 0;
+    let new_line = get_new_line(config.end_of_line);
     let mut should_remeasure = false;
-    let mut out: Out = vec![];
-    let mut line_suffixes: LineSuffixes<'a> = vec![];
-    let mut group_mode_map: GroupModeMap = HashMap::new();
-    let mut commands: Commands = vec![(root_indent(), Mode::Break, doc)];
-
-    while !commands.is_empty() {
-        let (indent, mode, doc) = commands.pop().unwrap();
+    let mut out = Out::default();
+    let mut line_suffixes: LineSuffixes = vec![];
+    let mut group_mode_map: GroupModeMap = vec![None; arena.group_id_count()];
+    let mut commands: Commands = vec![(root_indent(), Mode::Break, PrintItem::Doc(root))];
+    let mut fits_cache = FitsCache::new();
 
-        match doc.borrow() {
-            Doc::String(string) => {
-                out.push(OutKind::String(string.to_string()));
-                pos += string.len();
+    loop {
+        let (indent, mode, item) = match commands.pop() {
+            Some(command) => command,
+            // Like Prettier, line suffixes that are left at the end of the doc are printed.
+            None if !line_suffixes.is_empty() => {
+                commands.extend(line_suffixes.drain(..).rev());
+                continue;
             }
-            Doc::Children(children) => {
-                for child in children.iter().rev() {
-                    commands.push((Rc::clone(&indent), mode, Rc::clone(child)));
+            None => break,
+        };
+        match item.resolve(arena) {
+            ArenaDoc::String(span) => {
+                let string = arena.get_text(span);
+                let width = get_string_width(string);
+                // Like Prettier, line endings inside of strings use the configured line ending.
+                let converted = convert_end_of_line(string, new_line);
+                // Whitespace is trimmed at the end of a line, so it can't make a line too wide.
+                if diagnostics.is_some()
+                    && overflow.is_none()
+                    && pos + width > config.print_width
+                    && !string.trim().is_empty()
+                {
+                    let offset = emitted + out.text.len();
+                    overflow = Some(LineWidthDiagnostic {
+                        line,
+                        width: 0,
+                        string: string.to_string(),
+                        output: offset..offset + converted.len(),
+                    });
                 }
+                out.text.push_str(&converted);
+                pos += width;
             }
-            Doc::Command(command) => match command {
-                DocCommand::Indent(contents) => {
-                    commands.push((make_indent(indent, config), mode, Rc::clone(contents)));
+            ArenaDoc::Children(children) => {
+                for child in arena.get_children(children).iter().rev() {
+                    commands.push((Rc::clone(&indent), mode, PrintItem::Doc(*child)));
                 }
-                DocCommand::Align(contents, width) => {
+            }
+            ArenaDoc::Indent(contents) => {
+                commands.push((make_indent(indent, config), mode, PrintItem::Doc(contents)));
+            }
+            ArenaDoc::Align(contents, amount) => {
+                commands.push((
+                    make_align(indent, arena.get_align_amount(amount), config),
+                    mode,
+                    PrintItem::Doc(contents),
+                ));
+            }
+            ArenaDoc::Trim => {
+                pos = pos.saturating_sub(trim(&mut out, config));
+            }
+            ArenaDoc::Group {
+                contents,
+                should_break,
+                id,
+                expanded_states,
+            } => {
+                if mode == Mode::Flat && !should_remeasure {
                     commands.push((
-                        make_align(indent, width.clone(), config),
-                        mode,
-                        Rc::clone(contents),
+                        Rc::clone(&indent),
+                        if should_break {
+                            Mode::Break
+                        } else {
+                            Mode::Flat
+                        },
+                        PrintItem::Doc(contents),
                     ));
-                }
-                DocCommand::Trim => {
-                    pos -= trim(&mut out);
-                }
-                DocCommand::Group(contents, options) => {
-                    let mut should_insert_into_map = true;
-                    let next_mode = Mode::Flat;
-                    if mode == Mode::Flat && !should_remeasure {
-                        commands.push((
-                            Rc::clone(&indent),
-                            if options.should_break {
-                                Mode::Break
-                            } else {
-                                Mode::Flat
-                            },
-                            Rc::clone(contents),
-                        ));
-                    } else {
-                        should_remeasure = false;
-                        let next: Command = (Rc::clone(&indent), Mode::Flat, Rc::clone(contents));
-                        let remainder = PRINT_WIDTH - pos;
-                        let has_line_suffix = !line_suffixes.is_empty();
-                        if !options.should_break
-                            && fits(
-                                &next,
-                                &commands,
-                                remainder,
-                                options,
-                                has_line_suffix,
-                                false,
-                                config,
-                            )
-                        {
-                            commands.push(next);
-                        } else if !options.expanded_states.is_empty() {
-                            let most_expanded = options.expanded_states.last().unwrap();
-                            if options.should_break {
-                                commands.push((
-                                    Rc::clone(&indent),
-                                    Mode::Break,
-                                    Rc::clone(most_expanded),
-                                ));
-                            } else {
-                                let expanded_states = &options.expanded_states;
-                                for i in 1..(expanded_states.len() + 1) {
-                                    if i >= expanded_states.len() {
-                                        commands.push((
-                                            Rc::clone(&indent),
-                                            Mode::Flat,
-                                            Rc::clone(most_expanded),
-                                        ));
-                                        should_insert_into_map = false;
+                } else {
+                    should_remeasure = false;
+                    let next: Command = (Rc::clone(&indent), Mode::Flat, PrintItem::Doc(contents));
+                    let remainder = config.print_width.saturating_sub(pos);
+                    let has_line_suffix = !line_suffixes.is_empty();
+                    if !should_break
+                        && fits(
+                            &next,
+                            &commands,
+                            remainder,
+                            has_line_suffix,
+                            false,
+                            &group_mode_map,
+                            arena,
+                            config,
+                            &mut fits_cache,
+                        )
+                    {
+                        commands.push(next);
+                    } else if !expanded_states.is_empty() {
+                        let expanded_states = arena.get_children(expanded_states);
+                        let most_expanded = expanded_states[expanded_states.len() - 1];
+                        if should_break {
+                            commands.push((
+                                Rc::clone(&indent),
+                                Mode::Break,
+                                PrintItem::Doc(most_expanded),
+                            ));
+                        } else {
+                            for i in 1..(expanded_states.len() + 1) {
+                                if i >= expanded_states.len() {
+                                    commands.push((
+                                        Rc::clone(&indent),
+                                        Mode::Flat,
+                                        PrintItem::Doc(most_expanded),
+                                    ));
+                                    break;
+                                } else {
+                                    let command = (
+                                        Rc::clone(&indent),
+                                        Mode::Flat,
+                                        PrintItem::Doc(expanded_states[i]),
+                                    );
+                                    if fits(
+                                        &command,
+                                        &commands,
+                                        remainder,
+                                        has_line_suffix,
+                                        false,
+                                        &group_mode_map,
+                                        arena,
+                                        config,
+                                        &mut fits_cache,
+                                    ) {
+                                        commands.push(command);
                                         break;
-                                    } else {
-                                        let command = (
-                                            Rc::clone(&indent),
-                                            next_mode,
-                                            Rc::clone(&expanded_states[i]),
-                                        );
-                                        if fits(
-                                            &command,
-                                            &commands,
-                                            remainder,
-                                            options,
-                                            has_line_suffix,
-                                            false,
-                                            config,
-                                        ) {
-                                            commands.push(command);
-                                            should_insert_into_map = false;
-                                            break;
-                                        }
                                     }
                                 }
                             }
-                        } else {
-                            commands.push((indent, Mode::Break, Rc::clone(contents)));
                         }
-                    }
-                    if should_insert_into_map {
-                        group_mode_map.insert(options.id, next_mode);
+                    } else {
+                        commands.push((indent, Mode::Break, PrintItem::Doc(contents)));
                     }
                 }
-                DocCommand::Fill(contents, doc_options) => {
-                    let remainder = PRINT_WIDTH - pos;
-                    if !contents.is_empty() {
-                        let content = &contents[0];
-                        let contents_command_flat: Command =
-                            (Rc::clone(&indent), Mode::Flat, Rc::clone(content));
-                        let contents_command_break: Command =
-                            (Rc::clone(&indent), Mode::Break, Rc::clone(content));
-                        let content_fits = fits(
-                            &contents_command_flat,
-                            &Vec::new(),
-                            remainder,
-                            doc_options,
-                            !line_suffixes.is_empty(),
-                            true,
-                            config,
-                        );
-                        if contents.len() == 1 {
+                // Like Prettier, the group's mode is the mode of the command that was just pushed
+                // for it.
+                if let Some(id) = id {
+                    group_mode_map[id.index()] = commands.last().map(|(_, mode, _)| *mode);
+                }
+            }
+            ArenaDoc::Fill(contents) => {
+                let remainder = config.print_width.saturating_sub(pos);
+                if !contents.is_empty() {
+                    let parts = arena.get_children(contents);
+                    let content = PrintItem::Doc(parts[0]);
+                    let contents_command_flat: Command = (Rc::clone(&indent), Mode::Flat, content);
+                    let contents_command_break: Command =
+                        (Rc::clone(&indent), Mode::Break, content);
+                    // Like Prettier, the parts of a fill are measured without what comes after
+                    // them, except for the last content, which is measured along with the rest
+                    // of the doc so trailing punctuation doesn't overflow.
+                    let content_fits = fits(
+                        &contents_command_flat,
+                        if contents.len() == 1 { &commands } else { &[] },
+                        remainder,
+                        !line_suffixes.is_empty(),
+                        true,
+                        &group_mode_map,
+                        arena,
+                        config,
+                        &mut fits_cache,
+                    );
+                    if contents.len() == 1 {
+                        if content_fits {
+                            commands.push(contents_command_flat);
+                        } else {
+                            commands.push(contents_command_break);
+                        }
+                    } else {
+                        let whitespace = PrintItem::Doc(parts[1]);
+                        let whitespace_command_flat: Command =
+                            (Rc::clone(&indent), Mode::Flat, whitespace);
+                        let whitespace_command_break: Command =
+                            (Rc::clone(&indent), Mode::Break, whitespace);
+
+                        if contents.len() == 2 {
                             if content_fits {
                                 commands.push(contents_command_flat);
+                                commands.push(whitespace_command_flat);
                             } else {
                                 commands.push(contents_command_break);
+                                commands.push(whitespace_command_break);
                             }
                         } else {
-                            let whitespace = &contents[1];
-                            let whitespace_command_flat: Command =
-                                (Rc::clone(&indent), Mode::Flat, Rc::clone(whitespace));
-                            let whitespace_command_break: Command =
-                                (Rc::clone(&indent), Mode::Break, Rc::clone(whitespace));
-
-                            if contents.len() == 2 {
-                                if content_fits {
-                                    commands.push(contents_command_flat);
-                                    commands.push(whitespace_command_flat);
-                                } else {
-                                    commands.push(contents_command_break);
-                                    commands.push(whitespace_command_break);
-                                }
-                            } else {
-                                let mut cloned_contents = contents.clone();
-                                let item_0 = cloned_contents.remove(0);
-                                let item_1 = cloned_contents.remove(0);
-                                let first_and_second_content_flat_command: Command = (
-                                    Rc::clone(&indent),
-                                    Mode::Flat,
-                                    Rc::new(Doc::Children(vec![item_0, item_1])),
-                                );
-                                let first_and_second_content_fits = fits(
-                                    &first_and_second_content_flat_command,
-                                    &Vec::new(),
-                                    remainder,
-                                    doc_options,
-                                    !line_suffixes.is_empty(),
-                                    true,
-                                    config,
-                                );
-                                let remaining_command: Command = (
-                                    indent,
-                                    mode,
-                                    Rc::new(Doc::Command(DocCommand::Fill(
-                                        cloned_contents,
-                                        Rc::clone(doc_options),
-                                    ))),
-                                );
+                            let first_and_second_content_flat_command: Command = (
+                                Rc::clone(&indent),
+                                Mode::Flat,
+                                PrintItem::Children(contents.slice(0, 3)),
+                            );
+                            let first_and_second_content_fits = fits(
+                                &first_and_second_content_flat_command,
+                                if contents.len() == 3 { &commands } else { &[] },
+                                remainder,
+                                !line_suffixes.is_empty(),
+                                true,
+                                &group_mode_map,
+                                arena,
+                                config,
+                                &mut fits_cache,
+                            );
+                            let remaining_command: Command = (
+                                indent,
+                                mode,
+                                PrintItem::Fill(contents.slice(2, contents.len())),
+                            );
 
-                                commands.push(remaining_command);
+                            commands.push(remaining_command);
 
-                                if first_and_second_content_fits {
-                                    commands.push(whitespace_command_flat);
-                                    commands.push(contents_command_flat);
-                                } else if content_fits {
-                                    commands.push(whitespace_command_break);
-                                    commands.push(contents_command_flat);
-                                } else {
-                                    commands.push(whitespace_command_break);
-                                    commands.push(contents_command_break);
-                                }
+                            if first_and_second_content_fits {
+                                commands.push(whitespace_command_flat);
+                                commands.push(contents_command_flat);
+                            } else if content_fits {
+                                commands.push(whitespace_command_break);
+                                commands.push(contents_command_flat);
+                            } else {
+                                commands.push(whitespace_command_break);
+                                commands.push(contents_command_break);
                             }
                         }
                     }
                 }
-                DocCommand::IfBreak(break_contents, flat_contents, group_id) => {
-                    let group_mode = match group_mode_map.get(group_id as &str) {
-                        Some(mapped_mode) => mapped_mode,
-                        None => &mode,
-                    };
-                    match group_mode {
-                        Mode::Break => {
-                            commands.push((indent, mode, Rc::clone(break_contents)));
-                        }
-                        Mode::Flat => {
-                            commands.push((indent, mode, Rc::clone(flat_contents)));
-                        }
+            }
+            ArenaDoc::IfBreak(break_contents, flat_contents, group_id) => {
+                let group_mode = group_id
+                    .and_then(|id| group_mode_map[id.index()])
+                    .unwrap_or(mode);
+                match group_mode {
+                    Mode::Break => {
+                        commands.push((indent, mode, PrintItem::Doc(break_contents)));
                     }
-                }
-                DocCommand::IndentIfBreak(contents, group_id, negate) => {
-                    let group_mode = match group_mode_map.get(group_id as &str) {
-                        Some(mapped_mode) => mapped_mode,
-                        None => &mode,
-                    };
-                    match group_mode {
-                        Mode::Break => {
-                            if *negate {
-                                commands.push((indent, mode, Rc::clone(contents)));
-                            } else {
-                                commands.push((indent, mode, build_indent(Rc::clone(contents))));
-                            }
-                        }
-                        Mode::Flat => {
-                            if *negate {
-                                commands.push((indent, mode, build_indent(Rc::clone(contents))));
-                            } else {
-                                commands.push((indent, mode, Rc::clone(contents)));
-                            }
-                        }
+                    Mode::Flat => {
+                        commands.push((indent, mode, PrintItem::Doc(flat_contents)));
                     }
                 }
-                DocCommand::LineSuffix(contents) => {
-                    line_suffixes.push(contents);
+            }
+            ArenaDoc::IndentIfBreak(contents, group_id, negate) => {
+                let group_mode = group_mode_map[group_id.index()].unwrap_or(mode);
+                let should_indent = match group_mode {
+                    Mode::Break => !negate,
+                    Mode::Flat => negate,
+                };
+                if should_indent {
+                    commands.push((make_indent(indent, config), mode, PrintItem::Doc(contents)));
+                } else {
+                    commands.push((indent, mode, PrintItem::Doc(contents)));
                 }
-                DocCommand::LineSuffixBoundary => commands.push((
-                    indent,
-                    mode,
-                    Rc::new(Doc::Command(DocCommand::Line(LineMode::Hard))),
-                )),
-                DocCommand::Line(line_mode) => {
-                    if mode == Mode::Flat && *line_mode == LineMode::Auto {
-                        out.push(OutKind::String(String::from(" ")));
-                        pos += 1;
-                    } else if mode != Mode::Flat || *line_mode != LineMode::Soft {
-                        if *line_mode == LineMode::Hard || *line_mode == LineMode::HardLiteral {
-                            should_remeasure = true;
-                        }
-                        if line_suffixes.is_empty() {
-                            if *line_mode == LineMode::HardLiteral {
-                                out.push(OutKind::String(NEW_LINE.to_string()));
-                                pos = 0;
-                            } else {
-                                trim(&mut out);
-                                out.push(OutKind::String(NEW_LINE.to_string() + &indent.value));
-                                pos = indent.length;
-                            }
+            }
+            ArenaDoc::LineSuffix(contents) => {
+                line_suffixes.push((indent, mode, PrintItem::Doc(contents)));
+            }
+            ArenaDoc::LineSuffixBoundary => {
+                if !line_suffixes.is_empty() {
+                    commands.push((indent, mode, PrintItem::HardLine));
+                }
+            }
+            ArenaDoc::Line(line_mode) => {
+                if mode == Mode::Flat && line_mode == LineMode::Auto {
+                    out.text.push(' ');
+                    pos += 1;
+                } else if mode != Mode::Flat || line_mode != LineMode::Soft {
+                    if line_mode == LineMode::Hard || line_mode == LineMode::HardLiteral {
+                        should_remeasure = true;
+                    }
+                    if line_suffixes.is_empty() {
+                        let width = if line_mode == LineMode::HardLiteral {
+                            pos
                         } else {
-                            commands.push((Rc::clone(&indent), mode, doc));
-                            for suffix in line_suffixes.iter().rev() {
-                                commands.push((
-                                    Rc::clone(&indent),
-                                    mode,
-                                    Rc::new(Doc::String(suffix.to_string())),
-                                ));
+                            pos.saturating_sub(trim(&mut out, config))
+                        };
+                        end_line(
+                            diagnostics.as_deref_mut(),
+                            &mut overflow,
+                            &mut line,
+                            width,
+                            config,
+                        );
+                        emit(&out.text, &out.cursors)?;
+                        emitted += out.text.len();
+                        out.text.clear();
+                        out.cursors.clear();
+                        out.text.push_str(new_line);
+                        // Like Prettier, literal lines go back to the indentation marked as root.
+                        if line_mode == LineMode::HardLiteral {
+                            match &indent.root {
+                                Some(root) => {
+                                    out.text.push_str(&root.value);
+                                    pos = root.length;
+                                }
+                                None => pos = 0,
                             }
-                            line_suffixes.clear();
+                        } else {
+                            out.text.push_str(&indent.value);
+                            pos = indent.length;
                         }
+                    } else {
+                        commands.push((indent, mode, item));
+                        commands.extend(line_suffixes.drain(..).rev());
                     }
                 }
-                DocCommand::Cursor => {
-                    out.push(OutKind::Cursor);
+            }
+            ArenaDoc::Cursor => {
+                out.cursors.push(out.text.len());
+            }
+            ArenaDoc::SourceSpan(contents, start, end) => {
+                if let Some(mappings) = mappings.as_deref_mut() {
+                    let offset = emitted + out.text.len();
+                    mappings.push(SourceMapping {
+                        output: offset..offset,
+                        input: start..end,
+                    });
+                    commands.push((
+                        Rc::clone(&indent),
+                        mode,
+                        PrintItem::SourceSpanEnd(mappings.len() - 1),
+                    ));
                 }
-                DocCommand::BreakParent => {
-                    // ignore
+                commands.push((indent, mode, PrintItem::Doc(contents)));
+            }
+            ArenaDoc::SourceSpanEnd(index) => {
+                if let Some(mappings) = mappings.as_deref_mut() {
+                    mappings[index].output.end = emitted + out.text.len();
                 }
-            },
+            }
+            ArenaDoc::BreakParent => {
+                // Breaks are propagated to groups when they are added to the arena.
+            }
         }
     }
 
-    transform_out_to_string(out)
+    end_line(diagnostics, &mut overflow, &mut line, pos, config);
+    emit(&out.text, &out.cursors)
 }
 
-fn transform_out_to_string(out: Out) -> String {
-    let mut result = String::new();
-    for kind in out.into_iter() {
-        if let OutKind::String(string) = kind {
-            result.push_str(&string)
+pub fn print_arena_to_writer<W: Write>(
+    arena: &DocArena,
+    root: DocId,
+    config: &PrettifyConfig,
+    writer: &mut W,
+) -> io::Result<()> {
+    print_to_out(arena, root, config, None, None, |text, _| {
+        writer.write_all(text.as_bytes())
+    })
+}
+
+pub fn print_arena_to_string(arena: &DocArena, root: DocId, config: &PrettifyConfig) -> String {
+    let mut result = Vec::new();
+    print_arena_to_writer(arena, root, config, &mut result).unwrap();
+    // Only `str`s are written, so the result is valid UTF-8.
+    String::from_utf8(result).unwrap()
+}
+
+fn import_doc(arena: &mut DocArena, doc: &PrettifyDoc, config: &PrettifyConfig) -> DocId {
+    // Malformed docs are misformatted silently in release builds, so they fail while debugging
+    // unless the `validate` feature is turned off.
+    #[cfg(all(debug_assertions, feature = "validate"))]
+    {
+        let diagnostics = validate_doc(doc);
+        debug_assert!(
+            diagnostics.is_empty(),
+            "prettify: malformed doc: {}",
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    match config.layout_engine {
+        // The arena cleans the doc while importing it.
+        LayoutEngine::Greedy => arena.import(doc),
+        // The cheapest layout is chosen up front, so the greedy printer has no choices left.
+        LayoutEngine::Optimal => arena.import(&choose_cheapest_layout(
+            &propagate_breaks(clean_doc(doc)),
+            config,
+        )),
+    }
+}
+
+pub fn print_to_writer<W: Write>(
+    doc: PrettifyDoc,
+    config: &PrettifyConfig,
+    writer: &mut W,
+) -> io::Result<()> {
+    let mut arena = DocArena::new("");
+    let root = import_doc(&mut arena, &doc, config);
+    print_arena_to_writer(&arena, root, config, writer)
+}
+
+// `source` is the text `doc` was formatted from, if any, so text that is a copy of it can be
+// stored as spans of it and `EndOfLine::Auto` can be resolved.
+pub fn print_to_string(doc: PrettifyDoc, source: &str, config: &PrettifyConfig) -> String {
+    let config = &resolve_end_of_line(config, source);
+    let mut arena = DocArena::new(source);
+    let root = import_doc(&mut arena, &doc, config);
+    print_arena_to_string(&arena, root, config)
+}
+
+pub fn print_to_string_with_cursor(doc: PrettifyDoc, config: &PrettifyConfig) -> PrintedDoc {
+    let mut arena = DocArena::new("");
+    let root = import_doc(&mut arena, &doc, config);
+    let mut formatted = String::new();
+    let mut cursors = Vec::new();
+    let mut char_count = 0;
+    print_to_out(&arena, root, config, None, None, |text, offsets| {
+        for &offset in offsets {
+            cursors.push(CursorOffset {
+                byte: formatted.len() + offset,
+                char: char_count + text[..offset].chars().count(),
+            });
         }
+        char_count += text.chars().count();
+        formatted.push_str(text);
+        Ok(())
+    })
+    .unwrap();
+    PrintedDoc { formatted, cursors }
+}
+
+pub fn print_to_string_with_source_map(
+    doc: PrettifyDoc,
+    config: &PrettifyConfig,
+) -> SourceMappedDoc {
+    let mut arena = DocArena::new("");
+    let root = import_doc(&mut arena, &doc, config);
+    let mut formatted = String::new();
+    let mut mappings = Vec::new();
+    print_to_out(
+        &arena,
+        root,
+        config,
+        Some(&mut mappings),
+        None,
+        |text, _| {
+            formatted.push_str(text);
+            Ok(())
+        },
+    )
+    .unwrap();
+    // A trim can remove whitespace after a span already ended.
+    for mapping in mappings.iter_mut() {
+        mapping.output.start = mapping.output.start.min(formatted.len());
+        mapping.output.end = mapping.output.end.min(formatted.len());
+    }
+    SourceMappedDoc {
+        formatted,
+        mappings,
+    }
+}
+
+pub fn print_to_string_with_diagnostics(doc: PrettifyDoc, config: &PrettifyConfig) -> DiagnosedDoc {
+    let mut arena = DocArena::new("");
+    let root = import_doc(&mut arena, &doc, config);
+    let mut formatted = String::new();
+    let mut diagnostics = Vec::new();
+    print_to_out(
+        &arena,
+        root,
+        config,
+        None,
+        Some(&mut diagnostics),
+        |text, _| {
+            formatted.push_str(text);
+            Ok(())
+        },
+    )
+    .unwrap();
+    DiagnosedDoc {
+        formatted,
+        diagnostics,
     }
-    result
 }
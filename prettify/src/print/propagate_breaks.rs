@@ -0,0 +1,103 @@
+use super::super::doc::{Doc, DocCommand, DocOptions, DocPointer};
+use crate::PrettifyDoc;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+// Maps each visited doc to its rebuilt doc and whether it contains a break.
+type Checked = HashMap<*const Doc, (PrettifyDoc, bool)>;
+
+fn get_checked(checked: &Checked, doc: &PrettifyDoc) -> (PrettifyDoc, bool) {
+    checked[&DocPointer::as_ptr(doc)].clone()
+}
+
+// Returns the rebuilt docs, whether any of them changed and whether any of them contain a break.
+fn get_checked_all<'a>(
+    checked: &Checked,
+    docs: impl ExactSizeIterator<Item = &'a PrettifyDoc>,
+) -> (Vec<PrettifyDoc>, bool, bool) {
+    let mut changed = false;
+    let mut contains_break = false;
+    let mut result = Vec::with_capacity(docs.len());
+    for doc in docs {
+        let (checked_doc, doc_contains_break) = get_checked(checked, doc);
+        changed |= !DocPointer::ptr_eq(doc, &checked_doc);
+        contains_break |= doc_contains_break;
+        result.push(checked_doc);
+    }
+    (result, changed, contains_break)
+}
+
+// Returns whether a group breaks, given whether its contents contain a break. Like Prettier, a
+// conditional group picks one of its states while printing, so a break inside of a state only
+// breaks the groups within that state.
+pub(crate) fn group_breaks(should_break: bool, is_conditional: bool, contents_break: bool) -> bool {
+    should_break || (!is_conditional && contents_break)
+}
+
+// Rebuilds `doc` from its already checked children. Unchanged docs are reused as is.
+fn check(checked: &Checked, doc: &PrettifyDoc) -> (PrettifyDoc, bool) {
+    match doc.borrow() {
+        Doc::Command(DocCommand::Group(contents, options)) => {
+            let (new_contents, contents_break) = get_checked(checked, contents);
+            let (expanded_states, states_changed, _) =
+                get_checked_all(checked, options.expanded_states.iter());
+            let should_break = group_breaks(
+                options.should_break,
+                !options.expanded_states.is_empty(),
+                contents_break,
+            );
+            if should_break == options.should_break
+                && DocPointer::ptr_eq(contents, &new_contents)
+                && !states_changed
+            {
+                return (DocPointer::clone(doc), should_break);
+            }
+            let options = DocPointer::new(DocOptions {
+                should_break,
+                id: options.id,
+                expanded_states,
+            });
+            (
+                DocPointer::new(Doc::Command(DocCommand::Group(new_contents, options))),
+                should_break,
+            )
+        }
+        Doc::Command(DocCommand::BreakParent) => (DocPointer::clone(doc), true),
+        _ => {
+            let (children, changed, contains_break) = get_checked_all(checked, doc.get_children());
+            if !changed {
+                return (DocPointer::clone(doc), contains_break);
+            }
+            (DocPointer::new(doc.with_children(children)), contains_break)
+        }
+    }
+}
+
+// Port of Prettier's propagateBreaks:
+// https://github.com/prettier/prettier/blob/main/src/document/utils.js
+// Marks every group that contains a `break_parent()` (e.g., from a `hard_line()`) as
+// `should_break`, along with all of the groups around it. Docs are immutable, so the
+// affected docs are rebuilt. Shared subtrees are only visited once and stay shared.
+pub fn propagate_breaks(doc: PrettifyDoc) -> PrettifyDoc {
+    let mut checked: Checked = HashMap::new();
+    // (doc, whether its children have been checked)
+    let mut stack = vec![(DocPointer::clone(&doc), false)];
+    while let Some((current, children_checked)) = stack.pop() {
+        let key = DocPointer::as_ptr(&current);
+        if checked.contains_key(&key) {
+            continue;
+        }
+        if children_checked {
+            let result = check(&checked, &current);
+            checked.insert(key, result);
+            continue;
+        }
+        stack.push((DocPointer::clone(&current), true));
+        for child in current.get_children().rev() {
+            if !checked.contains_key(&DocPointer::as_ptr(child)) {
+                stack.push((DocPointer::clone(child), false));
+            }
+        }
+    }
+    checked.remove(&DocPointer::as_ptr(&doc)).unwrap().0
+}
@@ -1,9 +1,7 @@
-use super::super::doc::Doc;
+use super::super::arena::{ArenaDoc, DocArena, DocId, DocRange};
+use super::super::doc::LineMode;
 use std::rc::Rc;
 
-pub const PRINT_WIDTH: usize = 80;
-pub const NEW_LINE: char = '\n';
-
 #[derive(PartialEq, Debug, Clone)]
 pub enum IndentKind {
     Indent,
@@ -18,22 +16,49 @@ pub struct Indent {
     pub length: usize,
     pub queue: Vec<Rc<Indent>>,
     pub kind: Option<IndentKind>,
+    // The indentation marked by the closest `AlignAmount::MarkAsRoot`.
+    pub root: Option<Rc<Indent>>,
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub enum Mode {
     Break,
     Flat,
 }
 
-#[derive(PartialEq, Debug, Clone)]
-pub enum OutKind {
-    String(String),
-    Cursor,
+// The printed text that hasn't been emitted yet, and the byte offsets of the cursors in it.
+#[derive(PartialEq, Debug, Default)]
+pub struct Out {
+    pub text: String,
+    pub cursors: Vec<usize>,
+}
+
+// A doc in the arena, or a doc the printer creates while printing without adding it to the
+// arena.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum PrintItem {
+    Doc(DocId),
+    Children(DocRange),
+    Fill(DocRange),
+    HardLine,
+    // The index of the mapping whose output ends here.
+    SourceSpanEnd(usize),
+}
+
+impl PrintItem {
+    pub fn resolve(&self, arena: &DocArena) -> ArenaDoc {
+        match *self {
+            PrintItem::Doc(id) => arena.get(id),
+            PrintItem::Children(range) => ArenaDoc::Children(range),
+            PrintItem::Fill(range) => ArenaDoc::Fill(range),
+            PrintItem::HardLine => ArenaDoc::Line(LineMode::Hard),
+            PrintItem::SourceSpanEnd(index) => ArenaDoc::SourceSpanEnd(index),
+        }
+    }
 }
 
-pub type LineSuffixes<'a> = Vec<&'a str>;
-pub type Out = Vec<OutKind>;
-pub type Command<'a> = (Rc<Indent>, Mode, Rc<Doc<'a>>);
-pub type Commands<'a> = Vec<Command<'a>>;
-pub type GroupModeMap<'a> = std::collections::HashMap<&'a str, Mode>;
+pub type LineSuffixes = Vec<Command>;
+pub type Command = (Rc<Indent>, Mode, PrintItem);
+pub type Commands = Vec<Command>;
+// Indexed by group id.
+pub type GroupModeMap = Vec<Option<Mode>>;
@@ -0,0 +1,33 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+// Measures how many terminal columns a string takes up instead of how many bytes it
+// is. Each grapheme cluster is measured on its own so combining marks and emoji joined
+// with zero-width joiners collapse into a single glyph, which is at most 2 columns wide
+// (e.g., East Asian wide characters and emoji).
+pub fn get_string_width(text: &str) -> usize {
+    if text.is_ascii() {
+        return text.len();
+    }
+    text.graphemes(true)
+        .map(|grapheme| std::cmp::min(grapheme.width(), 2))
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get_string_width_test() {
+        assert_eq!(get_string_width(""), 0);
+        assert_eq!(get_string_width("hello"), 5);
+        assert_eq!(get_string_width("café"), 4);
+        assert_eq!(get_string_width("cafe\u{301}"), 4);
+        assert_eq!(get_string_width("日本語"), 6);
+        assert_eq!(get_string_width("ｈｅｌｌｏ"), 10);
+        assert_eq!(get_string_width("👍"), 2);
+        assert_eq!(get_string_width("👨\u{200d}👩\u{200d}👧"), 2);
+        assert_eq!(get_string_width("a\u{200b}b"), 2);
+    }
+}
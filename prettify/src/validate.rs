@@ -0,0 +1,166 @@
+use super::doc::{AlignAmount, Doc, DocCommand, DocPointer, GroupId, LineMode};
+use super::doc_utils::get_traversed_children;
+use crate::PrettifyDoc;
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum DocDiagnosticKind {
+    // A fill alternates between content and separators, so it needs an odd number of parts.
+    FillWithEvenParts(usize),
+    // An `if_break` or `indent_if_break` that refers to a group that isn't in the doc.
+    UnknownGroupId(GroupId),
+    // A dedent without any indentation to remove.
+    DedentOnRoot,
+    // Fill contents are measured as flat, so a hard line that isn't inside of a group makes
+    // them look like they fit.
+    HardLineInFlatFill,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct DocDiagnostic {
+    pub kind: DocDiagnosticKind,
+    // The child indexes from the root to the doc with the mistake. Conditional groups are
+    // indexed by their expanded states.
+    pub path: Vec<usize>,
+}
+
+impl fmt::Display for DocDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            DocDiagnosticKind::FillWithEvenParts(parts) => write!(
+                f,
+                "fill has {} parts, but it needs an odd number of parts",
+                parts
+            )?,
+            DocDiagnosticKind::UnknownGroupId(id) => {
+                write!(f, "{:?} doesn't belong to any group in the doc", id)?
+            }
+            DocDiagnosticKind::DedentOnRoot => write!(f, "dedent on root indentation")?,
+            DocDiagnosticKind::HardLineInFlatFill => {
+                write!(f, "hard line in fill contents that must be flat")?
+            }
+        }
+        write!(f, " at {:?}", self.path)
+    }
+}
+
+struct Node {
+    parent: Option<usize>,
+    index: usize,
+}
+
+fn get_path(nodes: &[Node], node: usize) -> Vec<usize> {
+    let mut path = Vec::new();
+    let mut current = Some(node);
+    while let Some(node) = current {
+        if let Some(parent) = nodes[node].parent {
+            path.push(nodes[node].index);
+            current = Some(parent);
+        } else {
+            current = None;
+        }
+    }
+    path.reverse();
+    path
+}
+
+// Returns the mistakes in `doc` that make the printer misformat it or panic. An empty list
+// means the doc is valid.
+pub fn validate_doc(doc: &PrettifyDoc) -> Vec<DocDiagnostic> {
+    let mut diagnostics: Vec<(DocDiagnosticKind, usize)> = Vec::new();
+    let mut group_ids: HashSet<GroupId> = HashSet::new();
+    let mut group_id_references: Vec<(GroupId, usize)> = Vec::new();
+    let mut nodes = vec![Node {
+        parent: None,
+        index: 0,
+    }];
+    // (doc, node, indentation depth, depth of the closest mark, whether it's inside of fill
+    // contents that must be flat)
+    let mut stack = vec![(doc, 0, 0, 0, false)];
+    // Shared docs, e.g., the states of nested conditional groups, are only checked once for
+    // each indentation and fill they appear in.
+    let mut visited: HashSet<(*const Doc, usize, usize, bool)> = HashSet::new();
+    while let Some((current, node, depth, mark_depth, in_flat_fill)) = stack.pop() {
+        if !visited.insert((DocPointer::as_ptr(current), depth, mark_depth, in_flat_fill)) {
+            continue;
+        }
+        let mut child_depth = depth;
+        let mut child_mark_depth = mark_depth;
+        let mut child_in_flat_fill = in_flat_fill;
+        match current.as_ref() {
+            Doc::Command(DocCommand::Group(_, options)) => {
+                if let Some(id) = options.id {
+                    group_ids.insert(id);
+                }
+                child_in_flat_fill = false;
+            }
+            Doc::Command(DocCommand::Fill(parts, _))
+                if parts.len() % 2 == 0 && !parts.is_empty() =>
+            {
+                diagnostics.push((DocDiagnosticKind::FillWithEvenParts(parts.len()), node));
+            }
+            Doc::Command(DocCommand::IfBreak(_, _, Some(id))) => {
+                group_id_references.push((*id, node));
+            }
+            Doc::Command(DocCommand::IndentIfBreak(_, id, _)) => {
+                group_id_references.push((*id, node));
+                child_depth += 1;
+            }
+            Doc::Command(DocCommand::Indent(_)) => child_depth += 1,
+            Doc::Command(DocCommand::Align(_, amount)) => match amount {
+                AlignAmount::Dedent if depth == 0 => {
+                    diagnostics.push((DocDiagnosticKind::DedentOnRoot, node));
+                }
+                AlignAmount::Dedent => child_depth -= 1,
+                AlignAmount::DedentToRoot => {
+                    child_depth = 0;
+                    child_mark_depth = 0;
+                }
+                AlignAmount::DedentToMark => child_depth = mark_depth,
+                AlignAmount::MarkAsRoot => child_mark_depth = depth,
+                AlignAmount::Spaces(_) | AlignAmount::String(_) | AlignAmount::IndentBy(_) => {
+                    child_depth += 1
+                }
+            },
+            Doc::Command(DocCommand::Line(LineMode::Hard | LineMode::HardLiteral))
+                if in_flat_fill =>
+            {
+                diagnostics.push((DocDiagnosticKind::HardLineInFlatFill, node));
+            }
+            _ => {}
+        }
+        let is_fill = matches!(current.as_ref(), Doc::Command(DocCommand::Fill(_, _)));
+        for (index, child) in get_traversed_children(current).enumerate().rev() {
+            nodes.push(Node {
+                parent: Some(node),
+                index,
+            });
+            // Only the contents of a fill are measured as flat, not its separators.
+            let in_flat_fill = if is_fill {
+                index % 2 == 0
+            } else {
+                child_in_flat_fill
+            };
+            stack.push((
+                child,
+                nodes.len() - 1,
+                child_depth,
+                child_mark_depth,
+                in_flat_fill,
+            ));
+        }
+    }
+    for (id, node) in group_id_references {
+        if !group_ids.contains(&id) {
+            diagnostics.push((DocDiagnosticKind::UnknownGroupId(id), node));
+        }
+    }
+    diagnostics
+        .into_iter()
+        .map(|(kind, node)| DocDiagnostic {
+            kind,
+            path: get_path(&nodes, node),
+        })
+        .collect()
+}
@@ -0,0 +1,184 @@
+// Docs whose printed output is checked by the printer tests. The layout engine tests print each
+// of them with both engines, so they're shared instead of copied. Each test binary only uses
+// some of them.
+#![allow(dead_code)]
+
+use prettify::{
+    align, concat, conditional_group, fill, group, group_with_options, hard_line, if_break, indent,
+    indent_by, indent_if_break, join, join_to_vector, line, line_suffix, line_suffix_boundary,
+    literal_line, mark_as_root, soft_line, string, AlignAmount, DocOptions, GroupIdAllocator,
+    PrettifyDoc,
+};
+
+fn array(items: Vec<PrettifyDoc>) -> PrettifyDoc {
+    concat(vec![
+        string("["),
+        indent(concat(vec![
+            soft_line(),
+            join(items, concat(vec![string(","), line()])),
+            if_break(string(","), string(""), None),
+        ])),
+        soft_line(),
+        string("]"),
+    ])
+}
+
+pub fn array_of_items(count: usize) -> PrettifyDoc {
+    group(array(
+        (0..count).map(|i| string(format!("item{}", i))).collect(),
+    ))
+}
+
+pub fn nested_arrays() -> PrettifyDoc {
+    let mut items: Vec<PrettifyDoc> = (0..12).map(|i| string(format!("item{}", i))).collect();
+    items.push(array(vec![string("itemA"), string("itemB")]));
+    group(array(items))
+}
+
+pub fn indent_by_width() -> PrettifyDoc {
+    indent(concat(vec![
+        hard_line(),
+        string("document"),
+        indent_by(
+            2,
+            concat(vec![
+                hard_line(),
+                string("nested"),
+                indent(concat(vec![hard_line(), string("indented")])),
+            ]),
+        ),
+    ]))
+}
+
+pub fn string_align() -> PrettifyDoc {
+    group(align(
+        concat(vec![hard_line(), string("aligned")]),
+        AlignAmount::String("----".to_string()),
+    ))
+}
+
+pub fn fill_list_of_items() -> PrettifyDoc {
+    let items = (0..30).map(|i| string(format!("item {},", i))).collect();
+    fill(join_to_vector(items, line()))
+}
+
+pub fn conditional_group_of_strings() -> PrettifyDoc {
+    conditional_group(
+        vec![
+            string("this is a very, very long line that is definitely over the eighty character limit for a single line so it should not be displayed."),
+            string("this is a very, very long line that is definitely over the eighty character limit for a single line so it should not be displayed."),
+            string("this is short enough so it will print."),
+            string("this will not print."),
+        ],
+        None,
+    )
+}
+
+// A heading that is printed as an ATX heading when it fits and as a setext heading otherwise.
+pub fn conditional_group_of_blocks() -> PrettifyDoc {
+    let text = "this is a very, very long line that is definitely over the eighty character limit for a single line but it's the only item so it will print.";
+    let words: Vec<PrettifyDoc> = text.split(' ').map(string).collect();
+    concat(vec![
+        conditional_group(
+            vec![
+                concat(vec![
+                    string("## "),
+                    concat(join_to_vector(words.clone(), line())),
+                ]),
+                concat(vec![
+                    fill(join_to_vector(words, line())),
+                    hard_line(),
+                    string("------------"),
+                ]),
+            ],
+            None,
+        ),
+        hard_line(),
+    ])
+}
+
+pub fn if_break_with_group_ids() -> PrettifyDoc {
+    let group_ids = GroupIdAllocator::new();
+    let broken = group_ids.allocate();
+    let flat = group_ids.allocate();
+    concat(vec![
+        group_with_options(
+            concat(vec![string("a"), hard_line()]),
+            DocOptions {
+                id: Some(broken),
+                ..DocOptions::default()
+            },
+        ),
+        group_with_options(
+            string("b"),
+            DocOptions {
+                id: Some(flat),
+                ..DocOptions::default()
+            },
+        ),
+        group(concat(vec![
+            if_break(string("broken"), string("flat"), Some(broken)),
+            string(" "),
+            if_break(string("broken"), string("flat"), Some(flat)),
+        ])),
+    ])
+}
+
+pub fn indent_if_break_with_group_id() -> PrettifyDoc {
+    let group_ids = GroupIdAllocator::new();
+    let id = group_ids.allocate();
+    concat(vec![
+        group_with_options(
+            concat(vec![string("a"), line(), string("b")]),
+            DocOptions {
+                should_break: true,
+                id: Some(id),
+                ..DocOptions::default()
+            },
+        ),
+        indent_if_break(concat(vec![hard_line(), string("c")]), id, false),
+        indent_if_break(concat(vec![hard_line(), string("d")]), id, true),
+    ])
+}
+
+pub fn line_suffix_with_boundary() -> PrettifyDoc {
+    group(concat(vec![
+        string("{"),
+        line_suffix(string(" // comment")),
+        line_suffix_boundary(),
+        string("}"),
+        hard_line(),
+    ]))
+}
+
+pub fn literal_line_in_marked_root() -> PrettifyDoc {
+    indent(mark_as_root(concat(vec![
+        string("a"),
+        literal_line(),
+        string("b"),
+    ])))
+}
+
+// Every fixture, by name.
+pub fn all() -> Vec<(&'static str, PrettifyDoc)> {
+    vec![
+        ("array that fits", array_of_items(5)),
+        ("array that breaks", array_of_items(12)),
+        ("nested arrays", nested_arrays()),
+        ("indent by width", indent_by_width()),
+        ("string align", string_align()),
+        ("fill", fill_list_of_items()),
+        (
+            "conditional group of strings",
+            conditional_group_of_strings(),
+        ),
+        ("conditional group of blocks", conditional_group_of_blocks()),
+        ("if break with group ids", if_break_with_group_ids()),
+        (
+            "indent if break with group id",
+            indent_if_break_with_group_id(),
+        ),
+        ("line suffix with boundary", line_suffix_with_boundary()),
+        ("literal line in marked root", literal_line_in_marked_root()),
+    ]
+}
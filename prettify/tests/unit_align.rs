@@ -1,3 +1,5 @@
+mod fixtures;
+
 use prettify::{align, concat, group, hard_line, print, string, AlignAmount};
 
 #[test]
@@ -20,11 +22,5 @@ fn space_align() {
 
 #[test]
 fn string_align() {
-    assert_eq!(
-        print(group(align(
-            concat(vec![hard_line(), string("aligned")]),
-            AlignAmount::String("----".to_string())
-        ))),
-        "\n----aligned".to_string()
-    );
+    assert_eq!(print(fixtures::string_align()), "\n----aligned".to_string());
 }
@@ -0,0 +1,78 @@
+use prettify::{
+    concat, fill, group, indent, join, join_to_vector, line, print, print_arena, soft_line, string,
+    DocArena, PRETTIFY_DEFAULT_CONFIG,
+};
+
+#[test]
+fn arena_builders_match_builders() {
+    let source = "[item1, item2, item3]";
+    let items = ["item1", "item2", "item3"];
+    let mut arena = DocArena::new(source);
+    let open = arena.string(&source[..1]);
+    let close = arena.string(&source[source.len() - 1..]);
+    let item_ids = items
+        .iter()
+        .map(|item| {
+            let start = source.find(item).unwrap();
+            arena.string(&source[start..start + item.len()])
+        })
+        .collect();
+    let comma = arena.string(",");
+    let arena_line = arena.line();
+    let separator = arena.concat(vec![comma, arena_line]);
+    let joined = arena.join(item_ids, separator);
+    let leading_soft_line = arena.soft_line();
+    let contents = arena.concat(vec![leading_soft_line, joined]);
+    let indented = arena.indent(contents);
+    let trailing_soft_line = arena.soft_line();
+    let brackets = arena.concat(vec![open, indented, trailing_soft_line, close]);
+    let root = arena.group(brackets);
+
+    let doc = group(concat(vec![
+        string("["),
+        indent(concat(vec![
+            soft_line(),
+            join(
+                items.iter().map(|item| string(*item)).collect(),
+                concat(vec![string(","), line()]),
+            ),
+        ])),
+        soft_line(),
+        string("]"),
+    ]));
+
+    assert_eq!(
+        print_arena(&arena, root, &PRETTIFY_DEFAULT_CONFIG),
+        print(doc)
+    );
+    assert_eq!(
+        print_arena(&arena, root, &PRETTIFY_DEFAULT_CONFIG),
+        "[item1, item2, item3]".to_string()
+    );
+}
+
+#[test]
+fn arena_hard_line_breaks_groups() {
+    let mut arena = DocArena::new("");
+    let first = arena.string("a");
+    let line = arena.line();
+    let second = arena.string("b");
+    let hard_line = arena.hard_line();
+    let contents = arena.concat(vec![first, line, second, hard_line]);
+    let root = arena.group(contents);
+    assert_eq!(
+        print_arena(&arena, root, &PRETTIFY_DEFAULT_CONFIG),
+        "a\nb\n".to_string()
+    );
+}
+
+#[test]
+fn large_fill() {
+    let mut items = Vec::new();
+    for _ in 0..20_000 {
+        items.push(string("item"));
+    }
+    let printed = print(fill(join_to_vector(items, line())));
+    assert_eq!(printed.lines().count(), 1_250);
+    assert!(printed.lines().all(|line| line.len() <= 80));
+}
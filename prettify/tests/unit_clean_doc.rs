@@ -0,0 +1,70 @@
+use prettify::{
+    clean_doc, concat, fill, group, hard_line, if_break, indent, line, print, print_doc_to_debug,
+    string, PrettifyDoc,
+};
+
+fn debug_clean(doc: PrettifyDoc) -> String {
+    print_doc_to_debug(clean_doc(&doc))
+}
+
+#[test]
+fn flattens_concats_and_merges_strings() {
+    assert_eq!(
+        debug_clean(concat(vec![
+            string("a"),
+            concat(vec![string(""), string("b"), concat(vec![string("c")])]),
+            line(),
+            string(""),
+        ])),
+        r#"concat(vec![string("abc"), line()])"#
+    );
+}
+
+#[test]
+fn removes_empty_docs() {
+    assert_eq!(
+        debug_clean(concat(vec![
+            group(string("")),
+            indent(concat(vec![])),
+            if_break(string(""), string(""), None),
+            fill(vec![string(""), string("")]),
+        ])),
+        r#"string("")"#
+    );
+}
+
+#[test]
+fn unwraps_nested_groups() {
+    assert_eq!(
+        debug_clean(group(group(concat(vec![string("a"), line(), string("b")])))),
+        r#"group(concat(vec![string("a"), line(), string("b")]))"#
+    );
+}
+
+#[test]
+fn keeps_shared_docs_shared() {
+    let shared = group(concat(vec![string("a"), concat(vec![line(), string("b")])]));
+    let cleaned = clean_doc(&concat(vec![PrettifyDoc::clone(&shared), shared]));
+    assert!(print_doc_to_debug(cleaned).starts_with("let shared_0"));
+}
+
+#[test]
+fn does_not_change_output() {
+    let doc = group(concat(vec![
+        string("["),
+        indent(concat(vec![
+            string(""),
+            line(),
+            concat(vec![string("a"), string(","), line(), string("b")]),
+        ])),
+        hard_line(),
+        string("]"),
+    ]));
+    assert_eq!(print(clean_doc(&doc)), print(doc));
+}
+
+#[test]
+fn unchanged_docs_are_reused() {
+    let doc = group(concat(vec![string("a"), line(), string("b")]));
+    assert!(PrettifyDoc::ptr_eq(&clean_doc(&doc), &doc));
+}
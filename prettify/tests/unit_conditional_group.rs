@@ -1,19 +1,11 @@
-use prettify::{
-    concat, conditional_group, fill, hard_line, join_to_vector, line, print, string, PrettifyDoc,
-};
+mod fixtures;
+
+use prettify::{concat, conditional_group, hard_line, print, string};
 
 #[test]
 fn conditional_group_command() {
     assert_eq!(
-        print(conditional_group(
-            vec![
-                string("this is a very, very long line that is definitely over the eighty character limit for a single line so it should not be displayed."),
-                string("this is a very, very long line that is definitely over the eighty character limit for a single line so it should not be displayed."),
-                string("this is short enough so it will print."),
-                string("this will not print.")
-            ],
-            "group"
-        )),
+        print(fixtures::conditional_group_of_strings()),
         "this is short enough so it will print.".to_string()
     );
 }
@@ -29,7 +21,7 @@ fn prints_first_if_it_fits() {
                 string("this is short enough so it will print."),
                 string("this will not print.")
             ],
-            "group"
+            None,
         )),
         "this is short and will print.".to_string()
     );
@@ -40,7 +32,7 @@ fn prints_only_item_if_fits() {
     assert_eq!(
         print(conditional_group(
             vec![string("this is short and will print.")],
-            "group"
+            None,
         )),
         "this is short and will print.".to_string()
     );
@@ -51,7 +43,7 @@ fn prints_only_item_even_if_not_fit() {
     assert_eq!(
         print(conditional_group(
             vec![string("this is a very, very long line that is definitely over the eighty character limit for a single line but it's the only item so it will print."),],
-            "group"
+            None,
         )),
         "this is a very, very long line that is definitely over the eighty character limit for a single line but it's the only item so it will print.".to_string()
     );
@@ -70,7 +62,7 @@ fn conditional_group_with_complex_blocks() {
                         string("------------")
                     ]),
                 ],
-                "block",
+                None,
             ),
             hard_line(),
         ])),
@@ -79,25 +71,8 @@ fn conditional_group_with_complex_blocks() {
 }
 #[test]
 fn conditional_group_with_complex_blocks_2() {
-    let text = "this is a very, very long line that is definitely over the eighty character limit for a single line but it's the only item so it will print.";
-    let words: Vec<PrettifyDoc> = text.replace('\n', " ").split(' ').map(string).collect();
     assert_eq!(
-        print(concat(vec![conditional_group(
-            vec![
-                concat(vec![
-                    string("## "),
-                    concat(join_to_vector(words.clone(), line()))
-                ]),
-                concat(vec![
-                    fill(join_to_vector(words, line())),
-                    hard_line(),
-                    string("------------")
-                ]),
-            ],
-            "block",
-        ),
-        hard_line(),
-    ])),
-        "this is a very, very long line that is definitely over the eighty character limit\nfor a single line but it's the only item so it will print.\n------------\n".to_string()
+        print(fixtures::conditional_group_of_blocks()),
+        "this is a very, very long line that is definitely over the eighty character\nlimit for a single line but it's the only item so it will print.\n------------\n".to_string()
     );
 }
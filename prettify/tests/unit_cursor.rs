@@ -0,0 +1,73 @@
+use prettify::{
+    concat, cursor, group, hard_line, indent, print, print_with_cursor, string, trim, CursorOffset,
+    PRETTIFY_DEFAULT_CONFIG,
+};
+
+#[test]
+fn cursor_is_not_printed() {
+    assert_eq!(
+        print(concat(vec![string("hello"), cursor(), string(" world")])),
+        "hello world".to_string()
+    );
+}
+
+#[test]
+fn returns_cursor_offset() {
+    let printed = print_with_cursor(
+        concat(vec![string("hello"), cursor(), string(" world")]),
+        &PRETTIFY_DEFAULT_CONFIG,
+    );
+    assert_eq!(printed.formatted, "hello world".to_string());
+    assert_eq!(printed.cursors, vec![CursorOffset { byte: 5, char: 5 }]);
+}
+
+#[test]
+fn returns_every_cursor() {
+    let printed = print_with_cursor(
+        group(concat(vec![
+            cursor(),
+            string("{"),
+            indent(concat(vec![hard_line(), string("hello"), cursor()])),
+            hard_line(),
+            string("}"),
+            cursor(),
+        ])),
+        &PRETTIFY_DEFAULT_CONFIG,
+    );
+    assert_eq!(printed.formatted, "{\n    hello\n}".to_string());
+    assert_eq!(
+        printed.cursors,
+        vec![
+            CursorOffset { byte: 0, char: 0 },
+            CursorOffset { byte: 11, char: 11 },
+            CursorOffset { byte: 13, char: 13 },
+        ]
+    );
+}
+
+#[test]
+fn char_offset_differs_from_byte_offset() {
+    let printed = print_with_cursor(
+        concat(vec![string("café"), cursor(), string("!")]),
+        &PRETTIFY_DEFAULT_CONFIG,
+    );
+    assert_eq!(printed.formatted, "café!".to_string());
+    assert_eq!(printed.cursors, vec![CursorOffset { byte: 5, char: 4 }]);
+}
+
+#[test]
+fn no_cursor() {
+    let printed = print_with_cursor(string("hello"), &PRETTIFY_DEFAULT_CONFIG);
+    assert_eq!(printed.formatted, "hello".to_string());
+    assert_eq!(printed.cursors, vec![]);
+}
+
+#[test]
+fn trim_stops_at_cursor() {
+    let printed = print_with_cursor(
+        concat(vec![string("hello  "), cursor(), string("  "), trim()]),
+        &PRETTIFY_DEFAULT_CONFIG,
+    );
+    assert_eq!(printed.formatted, "hello  ".to_string());
+    assert_eq!(printed.cursors, vec![CursorOffset { byte: 7, char: 7 }]);
+}
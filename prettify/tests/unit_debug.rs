@@ -0,0 +1,91 @@
+use prettify::{
+    align, concat, conditional_group, fill, group, hard_line, if_break, indent, indent_if_break,
+    line, line_suffix, print_doc_to_debug, soft_line, string, AlignAmount, GroupIdAllocator,
+    PrettifyDoc,
+};
+
+#[test]
+fn renders_builder_calls() {
+    assert_eq!(
+        print_doc_to_debug(group(concat(vec![
+            string("{"),
+            indent(concat(vec![soft_line(), string("\"key\"")])),
+            soft_line(),
+            string("}"),
+        ]))),
+        indoc::indoc! {r#"
+            group(
+                concat(vec![
+                    string("{"),
+                    indent(concat(vec![soft_line(), string("\"key\"")])),
+                    soft_line(),
+                    string("}")
+                ])
+            )"#}
+        .to_string()
+    );
+}
+
+#[test]
+fn breaks_long_calls() {
+    assert_eq!(
+        print_doc_to_debug(concat(vec![
+            string("a long string that needs to be broken"),
+            hard_line(),
+            align(
+                fill(vec![string("b"), line(), string("c")]),
+                AlignAmount::Spaces(2)
+            ),
+        ])),
+        indoc::indoc! {r#"
+            concat(vec![
+                string("a long string that needs to be broken"),
+                hard_line(),
+                align(fill(vec![string("b"), line(), string("c")]), AlignAmount::Spaces(2))
+            ])"#}
+        .to_string()
+    );
+}
+
+#[test]
+fn labels_group_ids() {
+    assert_eq!(
+        print_doc_to_debug({
+            let group_ids = GroupIdAllocator::new();
+            let block = group_ids.allocate();
+            conditional_group(
+                vec![
+                    string("a"),
+                    if_break(string(","), string(""), Some(group_ids.allocate())),
+                    indent_if_break(string("b"), block, false),
+                ],
+                Some(block),
+            )
+        }),
+        indoc::indoc! {r#"
+            let group_ids = GroupIdAllocator::new();
+            let group_id_0 = group_ids.allocate();
+            let group_id_1 = group_ids.allocate();
+            conditional_group(
+                vec![
+                    string("a"),
+                    if_break(string(","), string(""), Some(group_id_0)),
+                    indent_if_break(string("b"), group_id_1, false)
+                ],
+                Some(group_id_1)
+            )"#}
+        .to_string()
+    );
+}
+
+#[test]
+fn labels_shared_subtrees() {
+    let shared: PrettifyDoc = group(concat(vec![string("a"), line_suffix(string(" // b"))]));
+    assert_eq!(
+        print_doc_to_debug(concat(vec![PrettifyDoc::clone(&shared), shared])),
+        indoc::indoc! {r#"
+            let shared_0 = group(concat(vec![string("a"), line_suffix(string(" // b"))]));
+            concat(vec![PrettifyDoc::clone(&shared_0), PrettifyDoc::clone(&shared_0)])"#}
+        .to_string()
+    );
+}
@@ -1,8 +1,32 @@
-use prettify::{concat, dedent, group, hard_line, indent, print, string};
+use prettify::{
+    concat, dedent, dedent_to_mark, dedent_to_root, group, hard_line, indent, mark_as_root, print,
+    print_arena, string, DocArena, PRETTIFY_DEFAULT_CONFIG,
+};
 
+// Malformed docs are only printed as is in release builds, so this prints through an arena,
+// which isn't validated.
 #[test]
 fn dedent_from_root_does_nothing() {
-    assert_eq!(print(group(dedent(string("hello")))), "hello".to_string());
+    let mut arena = DocArena::new("");
+    let root = arena.import(&group(dedent(string("hello"))));
+    assert_eq!(
+        print_arena(&arena, root, &PRETTIFY_DEFAULT_CONFIG),
+        "hello".to_string()
+    );
+}
+
+#[test]
+#[cfg(all(debug_assertions, feature = "validate"))]
+#[should_panic(expected = "dedent on root indentation")]
+fn dedent_from_root_fails_while_debugging() {
+    print(group(dedent(string("hello"))));
+}
+
+// Unlike `dedent`, these are valid on the root indentation.
+#[test]
+fn dedent_to_root_and_mark_on_root() {
+    assert_eq!(print(dedent_to_root(string("a"))), "a".to_string());
+    assert_eq!(print(dedent_to_mark(string("b"))), "b".to_string());
 }
 
 #[test]
@@ -15,3 +39,44 @@ fn undoes_an_indent() {
         "\ndedent".to_string()
     );
 }
+
+#[test]
+fn dedent_to_root_removes_every_indent() {
+    assert_eq!(
+        print(indent(indent(concat(vec![
+            hard_line(),
+            string("indented"),
+            dedent_to_root(concat(vec![hard_line(), string("root")])),
+        ])))),
+        "\n        indented\nroot".to_string()
+    );
+}
+
+#[test]
+fn dedent_to_mark_returns_to_the_marked_indent() {
+    assert_eq!(
+        print(indent(concat(vec![
+            hard_line(),
+            string("marked"),
+            mark_as_root(indent(concat(vec![
+                hard_line(),
+                string("indented"),
+                dedent_to_mark(concat(vec![hard_line(), string("back")])),
+                dedent_to_root(concat(vec![hard_line(), string("root")])),
+            ]))),
+        ]))),
+        "\n    marked\n        indented\n    back\nroot".to_string()
+    );
+}
+
+#[test]
+fn dedent_to_mark_without_a_mark_goes_to_root() {
+    assert_eq!(
+        print(indent(concat(vec![
+            hard_line(),
+            string("indented"),
+            dedent_to_mark(concat(vec![hard_line(), string("root")])),
+        ]))),
+        "\n    indented\nroot".to_string()
+    );
+}
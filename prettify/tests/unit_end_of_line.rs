@@ -0,0 +1,123 @@
+use prettify::{
+    concat, group, guess_end_of_line, hard_line, indent, line, line_suffix, line_suffix_boundary,
+    literal_line, print, print_source_with_config, print_with_config, resolve_end_of_line, string,
+    EndOfLine, PrettifyConfig, PRETTIFY_DEFAULT_CONFIG,
+};
+
+fn config_with_end_of_line(end_of_line: EndOfLine) -> PrettifyConfig {
+    PrettifyConfig {
+        end_of_line,
+        ..PRETTIFY_DEFAULT_CONFIG
+    }
+}
+
+#[test]
+fn default_end_of_line_is_lf() {
+    assert_eq!(PRETTIFY_DEFAULT_CONFIG.end_of_line, EndOfLine::Lf);
+    assert_eq!(
+        print(concat(vec![string("a"), hard_line(), string("b")])),
+        "a\nb".to_string()
+    );
+}
+
+#[test]
+fn hard_and_literal_lines_use_end_of_line() {
+    let doc = || {
+        concat(vec![
+            string("a"),
+            indent(concat(vec![hard_line(), string("b")])),
+            literal_line(),
+            string("c"),
+        ])
+    };
+    assert_eq!(
+        print_with_config(doc(), &config_with_end_of_line(EndOfLine::Crlf)),
+        "a\r\n    b\r\nc".to_string()
+    );
+    assert_eq!(
+        print_with_config(doc(), &config_with_end_of_line(EndOfLine::Cr)),
+        "a\r    b\rc".to_string()
+    );
+}
+
+#[test]
+fn broken_groups_use_end_of_line() {
+    let doc = group(concat(vec![string("first"), line(), string("second")]));
+    assert_eq!(
+        print_with_config(
+            doc,
+            &PrettifyConfig {
+                print_width: 10,
+                ..config_with_end_of_line(EndOfLine::Crlf)
+            }
+        ),
+        "first\r\nsecond".to_string()
+    );
+}
+
+#[test]
+fn line_suffix_flush_uses_end_of_line() {
+    let doc = concat(vec![
+        string("a"),
+        line_suffix(string(" // comment")),
+        string(","),
+        line_suffix_boundary(),
+        string("b"),
+    ]);
+    assert_eq!(
+        print_with_config(doc, &config_with_end_of_line(EndOfLine::Crlf)),
+        "a, // comment\r\nb".to_string()
+    );
+}
+
+#[test]
+fn guess_end_of_line_picks_most_common_ending() {
+    assert_eq!(guess_end_of_line("no line endings"), EndOfLine::Lf);
+    assert_eq!(guess_end_of_line("a\nb\n"), EndOfLine::Lf);
+    assert_eq!(guess_end_of_line("a\r\nb\r\n"), EndOfLine::Crlf);
+    assert_eq!(guess_end_of_line("a\rb\r"), EndOfLine::Cr);
+    assert_eq!(guess_end_of_line("a\r\nb\r\nc\n"), EndOfLine::Crlf);
+    assert_eq!(guess_end_of_line("a\r\nb\nc\n"), EndOfLine::Lf);
+}
+
+#[test]
+fn strings_use_end_of_line() {
+    let doc = || concat(vec![string("a\nb"), hard_line(), string("c\r\nd")]);
+    assert_eq!(
+        print_with_config(doc(), &config_with_end_of_line(EndOfLine::Crlf)),
+        "a\r\nb\r\nc\r\nd".to_string()
+    );
+    assert_eq!(
+        print_with_config(doc(), &config_with_end_of_line(EndOfLine::Cr)),
+        "a\rb\rc\rd".to_string()
+    );
+    assert_eq!(print(doc()), "a\nb\nc\nd".to_string());
+}
+
+#[test]
+fn auto_uses_most_common_ending_of_source() {
+    let doc = || concat(vec![string("a"), hard_line(), string("b")]);
+    let auto = config_with_end_of_line(EndOfLine::Auto);
+    assert_eq!(
+        print_source_with_config(doc(), "x\r\ny\r\n", &auto),
+        "a\r\nb".to_string()
+    );
+    assert_eq!(
+        print_source_with_config(doc(), "x\ry\r", &auto),
+        "a\rb".to_string()
+    );
+    // Without the source, there is nothing to detect.
+    assert_eq!(print_with_config(doc(), &auto), "a\nb".to_string());
+}
+
+#[test]
+fn resolve_end_of_line_keeps_explicit_ending() {
+    assert_eq!(
+        resolve_end_of_line(&config_with_end_of_line(EndOfLine::Auto), "a\r\n").end_of_line,
+        EndOfLine::Crlf
+    );
+    assert_eq!(
+        resolve_end_of_line(&config_with_end_of_line(EndOfLine::Cr), "a\r\n").end_of_line,
+        EndOfLine::Cr
+    );
+}
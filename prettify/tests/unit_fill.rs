@@ -1,4 +1,6 @@
-use prettify::{fill, join_to_vector, line, print, string};
+mod fixtures;
+
+use prettify::{concat, fill, group, hard_line, join_to_vector, line, print, string};
 
 #[test]
 fn fill_single_item() {
@@ -13,14 +15,52 @@ fn fill_long_item() {
 
 #[test]
 fn fill_list_of_items() {
-    let mut items = Vec::new();
-    for i in 0..30 {
-        items.push(string(format!("item {},", i)));
-    }
-    assert_eq!(print(
-        fill(
-            join_to_vector(items, line())
-        )
-    ),
+    assert_eq!(print(fixtures::fill_list_of_items()),
     "item 0, item 1, item 2, item 3, item 4, item 5, item 6, item 7, item 8, item 9,\nitem 10, item 11, item 12, item 13, item 14, item 15, item 16, item 17, item 18,\nitem 19, item 20, item 21, item 22, item 23, item 24, item 25, item 26, item 27,\nitem 28, item 29,".to_string());
 }
+
+#[test]
+fn fill_measures_next_content() {
+    let first = "a".repeat(70);
+    let second = "b".repeat(15);
+    assert_eq!(
+        print(fill(join_to_vector(
+            vec![string(&first), string(&second)],
+            line()
+        ))),
+        format!("{}\n{}", first, second)
+    );
+}
+
+#[test]
+fn fill_measures_rest_of_doc() {
+    let first = "a".repeat(74);
+    assert_eq!(
+        print(concat(vec![
+            fill(join_to_vector(
+                vec![string(&first), string("bbbbb")],
+                line()
+            )),
+            string(")"),
+        ])),
+        format!("{}\nbbbbb)", first)
+    );
+    assert_eq!(
+        print(fill(join_to_vector(
+            vec![string(&first), string("bbbbb")],
+            line()
+        ))),
+        format!("{} bbbbb", first)
+    );
+}
+
+#[test]
+fn fill_measures_rest_of_doc_up_to_its_first_line() {
+    assert_eq!(
+        print(concat(vec![
+            fill(join_to_vector(vec![string("aaa"), string("bbb")], line())),
+            group(concat(vec![string("x"), hard_line(), string("y")])),
+        ])),
+        "aaa bbbx\ny"
+    );
+}
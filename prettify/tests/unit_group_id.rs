@@ -0,0 +1,37 @@
+mod fixtures;
+
+use prettify::{concat, group, hard_line, if_break, print, string, GroupIdAllocator};
+
+#[test]
+fn if_break_uses_the_mode_of_its_group() {
+    assert_eq!(
+        print(fixtures::if_break_with_group_ids()),
+        "a\nbbroken flat"
+    );
+}
+
+#[test]
+fn if_break_without_group_id_uses_the_enclosing_group() {
+    assert_eq!(
+        print(group(concat(vec![
+            string("a"),
+            if_break(string("broken"), string("flat"), None),
+            hard_line(),
+        ]))),
+        "abroken\n"
+    );
+}
+
+#[test]
+fn indent_if_break_uses_the_mode_of_its_group() {
+    assert_eq!(
+        print(fixtures::indent_if_break_with_group_id()),
+        "a\nb\n    c\nd"
+    );
+}
+
+#[test]
+fn allocated_ids_are_unique() {
+    let group_ids = GroupIdAllocator::new();
+    assert_ne!(group_ids.allocate(), group_ids.allocate());
+}
@@ -5,7 +5,7 @@ fn if_break_break_state() {
     assert_eq!(
         print(group(concat(vec![
             string("hello "),
-            if_break(string("world"), string("again"), "doc_id".to_string()),
+            if_break(string("world"), string("again"), None),
             break_parent()
         ]))),
         "hello world".to_string()
@@ -17,7 +17,7 @@ fn if_break_flat_state() {
     assert_eq!(
         print(group(concat(vec![
             string("hello "),
-            if_break(string("world"), string("again"), "doc_id".to_string()),
+            if_break(string("world"), string("again"), None),
         ]))),
         "hello again".to_string()
     );
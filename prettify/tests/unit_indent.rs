@@ -1,5 +1,7 @@
+mod fixtures;
+
 use indoc::indoc;
-use prettify::{concat, group, hard_line, if_break, indent, join, line, print, soft_line, string};
+use prettify::{concat, group, hard_line, indent, print, string};
 
 #[test]
 fn indent_simple_string() {
@@ -11,21 +13,8 @@ fn indent_simple_string() {
 
 #[test]
 fn indent_array() {
-    let mut items = Vec::new();
-    for i in 0..12 {
-        items.push(string(format!("item{}", i)));
-    }
     assert_eq!(
-        print(group(concat(vec![
-            string("["),
-            indent(concat(vec![
-                soft_line(),
-                join(items, concat(vec![string(","), line()])),
-                if_break(string(","), string(""), String::from("comma")),
-            ])),
-            soft_line(),
-            string("]"),
-        ]))),
+        print(fixtures::array_of_items(12)),
         indoc! {r#"
             [
                 item0,
@@ -46,55 +35,16 @@ fn indent_array() {
 
 #[test]
 fn indents_array_only_when_wrapping_to_new_line() {
-    let mut items = Vec::new();
-    for i in 0..5 {
-        items.push(string(format!("item{}", i)));
-    }
     assert_eq!(
-        print(group(concat(vec![
-            string("["),
-            indent(concat(vec![
-                soft_line(),
-                join(items, concat(vec![string(","), line()])),
-                if_break(string(","), string(""), String::from("comma")),
-            ])),
-            soft_line(),
-            string("]"),
-        ]))),
+        print(fixtures::array_of_items(5)),
         "[item0, item1, item2, item3, item4]".to_string()
     );
 }
 
 #[test]
 fn nested_indents() {
-    let mut items = Vec::new();
-    for i in 0..12 {
-        items.push(string(format!("item{}", i)));
-    }
-    items.push(concat(vec![
-        string("["),
-        indent(concat(vec![
-            soft_line(),
-            join(
-                vec![string("itemA"), string("itemB")],
-                concat(vec![string(","), line()]),
-            ),
-            if_break(string(","), string(""), String::from("comma")),
-        ])),
-        soft_line(),
-        string("]"),
-    ]));
     assert_eq!(
-        print(group(concat(vec![
-            string("["),
-            indent(concat(vec![
-                soft_line(),
-                join(items, concat(vec![string(","), line()])),
-                if_break(string(","), string(""), String::from("comma")),
-            ])),
-            soft_line(),
-            string("]"),
-        ]))),
+        print(fixtures::nested_arrays()),
         indoc! {r#"
             [
                 item0,
@@ -116,3 +66,11 @@ fn nested_indents() {
             ]"#}
     );
 }
+
+#[test]
+fn indent_by_width() {
+    assert_eq!(
+        print(fixtures::indent_by_width()),
+        "\n    document\n      nested\n          indented".to_string()
+    );
+}
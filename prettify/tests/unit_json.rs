@@ -0,0 +1,110 @@
+use prettify::{
+    align, break_parent, concat, conditional_group, cursor, dedent, dedent_to_mark, dedent_to_root,
+    fill, from_json, group_with_options, hard_line, if_break, indent, indent_by, indent_if_break,
+    label, line, line_suffix, line_suffix_boundary, literal_line, mark_as_root, print,
+    print_doc_to_debug, soft_line, string, to_json, trim, AlignAmount, DocOptions,
+    GroupIdAllocator,
+};
+
+#[test]
+fn round_trips_every_doc() {
+    let group_ids = GroupIdAllocator::new();
+    let id = group_ids.allocate();
+    let doc = concat(vec![
+        group_with_options(
+            indent(concat(vec![string("a"), line(), soft_line()])),
+            DocOptions {
+                should_break: true,
+                id: Some(id),
+                ..DocOptions::default()
+            },
+        ),
+        conditional_group(vec![string("b"), string("c")], None),
+        fill(vec![string("d"), line(), string("e")]),
+        if_break(string(","), string(""), Some(id)),
+        if_break(string(","), string(""), None),
+        indent_if_break(string("f"), id, true),
+        break_parent(),
+        hard_line(),
+        literal_line(),
+        line_suffix(string(" // g")),
+        line_suffix_boundary(),
+        align(string("h"), AlignAmount::Spaces(2)),
+        align(string("i"), AlignAmount::String("> ".to_string())),
+        dedent(string("j")),
+        dedent_to_root(string("k")),
+        cursor(),
+        trim(),
+        label("member-chain", string("l")),
+        indent_by(2, string("m")),
+        mark_as_root(dedent_to_mark(string("n"))),
+    ]);
+    let json = to_json(&doc);
+    assert_eq!(
+        print_doc_to_debug(from_json(&json).unwrap()),
+        print_doc_to_debug(doc)
+    );
+}
+
+#[test]
+fn to_json_test() {
+    let doc = concat(vec![string("a"), indent(concat(vec![line(), string("b")]))]);
+    assert_eq!(
+        to_json(&doc),
+        indoc::indoc! {r#"
+            [
+              "a",
+              {
+                "contents": [
+                  {
+                    "type": "line"
+                  },
+                  "b"
+                ],
+                "type": "indent"
+              }
+            ]"#}
+    );
+}
+
+#[test]
+fn reads_prettier_docs() {
+    let json = r#"{
+        "type": "group",
+        "id": "array",
+        "contents": [
+            "[",
+            { "type": "indent", "contents": [{ "type": "line", "soft": true }, "1,", { "type": "line" }, "2"] },
+            { "type": "if-break", "breakContents": ",", "flatContents": "", "groupId": "array" },
+            { "type": "line", "soft": true },
+            "]"
+        ]
+    }"#;
+    assert_eq!(print(from_json(json).unwrap()), "[1, 2]");
+}
+
+#[test]
+fn from_json_errors() {
+    assert_eq!(
+        from_json(r#"{ "type": "unknown" }"#),
+        Err("unknown doc type \"unknown\"".to_string())
+    );
+    assert_eq!(
+        from_json(r#"{ "type": "indent" }"#),
+        Err(r#"missing "contents" in {"type":"indent"}"#.to_string())
+    );
+    assert!(from_json("[").is_err());
+}
+
+#[test]
+fn from_json_limits_depth() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(from_json(&nested(256)).is_ok());
+    assert!(from_json(&nested(257)).is_err());
+    assert_eq!(
+        from_json(&nested(200000)),
+        Err("docs can't nest deeper than 256 levels".to_string())
+    );
+    // Brackets in strings don't count.
+    assert!(from_json(&format!("[\"{}\"]", "[".repeat(1000))).is_ok());
+}
@@ -0,0 +1,35 @@
+use prettify::{
+    concat, get_label, group, label, line, print, print_doc_to_debug, string, PrettifyDoc,
+};
+
+#[test]
+fn label_does_not_change_output() {
+    let doc = group(concat(vec![
+        string("a"),
+        label("member-chain", concat(vec![line(), string("b")])),
+    ]));
+    assert_eq!(print(doc), "a b");
+}
+
+#[test]
+fn get_label_test() {
+    let labeled = label("member-chain", string("a"));
+    assert_eq!(get_label(&labeled), Some("member-chain"));
+    assert_eq!(get_label(&string("a")), None);
+}
+
+#[test]
+fn empty_label_returns_contents() {
+    let contents = string("a");
+    let labeled = label("", PrettifyDoc::clone(&contents));
+    assert!(PrettifyDoc::ptr_eq(&contents, &labeled));
+    assert_eq!(get_label(&labeled), None);
+}
+
+#[test]
+fn debug_label() {
+    assert_eq!(
+        print_doc_to_debug(label("member-chain", string("a"))),
+        r#"label("member-chain", string("a"))"#
+    );
+}
@@ -0,0 +1,197 @@
+mod fixtures;
+
+use prettify::{
+    concat, conditional_group, get_layout_cost, group, group_with_options, if_break, indent, join,
+    line, print_with_config, print_with_cursor, soft_line, string, DocOptions, GroupIdAllocator,
+    LayoutCost, LayoutEngine, PrettifyConfig, PrettifyDoc, PRETTIFY_DEFAULT_CONFIG,
+};
+
+const GREEDY_CONFIG: PrettifyConfig = PrettifyConfig {
+    print_width: 20,
+    ..PRETTIFY_DEFAULT_CONFIG
+};
+
+const OPTIMAL_CONFIG: PrettifyConfig = PrettifyConfig {
+    layout_engine: LayoutEngine::Optimal,
+    ..GREEDY_CONFIG
+};
+
+fn items(open: &str, items: Vec<&str>, close: &str) -> PrettifyDoc {
+    items_around(
+        open,
+        join(
+            items.into_iter().map(string).collect(),
+            concat(vec![string(","), line()]),
+        ),
+        close,
+    )
+}
+
+fn items_around(open: &str, contents: PrettifyDoc, close: &str) -> PrettifyDoc {
+    group(concat(vec![
+        string(open),
+        indent(concat(vec![soft_line(), contents])),
+        soft_line(),
+        string(close),
+    ]))
+}
+
+fn trailing_comma_items(items: Vec<&str>) -> PrettifyDoc {
+    let group_ids = GroupIdAllocator::new();
+    let id = group_ids.allocate();
+    group_with_options(
+        concat(vec![
+            string("["),
+            indent(concat(vec![
+                soft_line(),
+                join(
+                    items.into_iter().map(string).collect(),
+                    concat(vec![string(","), line()]),
+                ),
+                if_break(string(","), string(""), Some(id)),
+            ])),
+            soft_line(),
+            string("]"),
+        ]),
+        DocOptions {
+            id: Some(id),
+            ..DocOptions::default()
+        },
+    )
+}
+
+fn remove_whitespace(text: &str) -> String {
+    text.split_whitespace().collect()
+}
+
+// Prints the docs the other tests in this folder print with both engines, at their width and
+// at a narrow one. The optimal engine never costs more than the greedy one. Fixtures that are
+// printed differently are listed when run with `--nocapture`.
+#[test]
+fn compare_layout_engines() {
+    for (name, doc) in fixtures::all() {
+        for greedy_config in [PRETTIFY_DEFAULT_CONFIG, GREEDY_CONFIG] {
+            let optimal_config = PrettifyConfig {
+                layout_engine: LayoutEngine::Optimal,
+                ..greedy_config
+            };
+            let greedy = print_with_config(doc.clone(), &greedy_config);
+            let optimal = print_with_config(doc.clone(), &optimal_config);
+            let greedy_cost = get_layout_cost(&greedy, &greedy_config);
+            let optimal_cost = get_layout_cost(&optimal, &optimal_config);
+            assert!(
+                optimal_cost <= greedy_cost,
+                "{}: {:?} costs more than {:?}",
+                name,
+                optimal,
+                greedy
+            );
+            // The states of a conditional group don't have to contain the same text.
+            if !name.starts_with("conditional group") {
+                assert_eq!(
+                    remove_whitespace(&greedy),
+                    remove_whitespace(&optimal),
+                    "{}",
+                    name
+                );
+            }
+            if greedy != optimal {
+                println!(
+                    "{} at {} columns\n--- greedy {:?}\n{}\n--- optimal {:?}\n{}\n",
+                    name, greedy_config.print_width, greedy_cost, greedy, optimal_cost, optimal
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn breaks_the_group_that_overflows() {
+    let doc = concat(vec![
+        items("[", vec!["1", "2"], "]"),
+        items("(", vec!["aaaaaaaaaa", "bbbbbbbbbb"], ")"),
+    ]);
+    assert_eq!(
+        print_with_config(doc.clone(), &GREEDY_CONFIG),
+        "[\n    1,\n    2\n](\n    aaaaaaaaaa,\n    bbbbbbbbbb\n)"
+    );
+    assert_eq!(
+        print_with_config(doc, &OPTIMAL_CONFIG),
+        "[1, 2](\n    aaaaaaaaaa,\n    bbbbbbbbbb\n)"
+    );
+}
+
+#[test]
+fn matches_greedy_when_everything_fits() {
+    let doc = items("[", vec!["1", "2", "3"], "]");
+    assert_eq!(print_with_config(doc, &OPTIMAL_CONFIG), "[1, 2, 3]");
+}
+
+#[test]
+fn resolves_if_break_for_the_chosen_mode() {
+    assert_eq!(
+        print_with_config(
+            trailing_comma_items(vec!["item1", "item2", "item3"]),
+            &OPTIMAL_CONFIG
+        ),
+        "[\n    item1,\n    item2,\n    item3,\n]"
+    );
+    assert_eq!(
+        print_with_config(trailing_comma_items(vec!["1", "2"]), &OPTIMAL_CONFIG),
+        "[1, 2]"
+    );
+}
+
+#[test]
+fn prefers_the_first_conditional_state_that_fits() {
+    let doc = conditional_group(
+        vec![
+            string("this is far too long to fit"),
+            string("short enough"),
+            string("short"),
+        ],
+        None,
+    );
+    assert_eq!(print_with_config(doc, &OPTIMAL_CONFIG), "short enough");
+}
+
+#[test]
+fn keeps_cursors() {
+    let doc = concat(vec![
+        items("[", vec!["1", "2"], "]"),
+        prettify::cursor(),
+        items("(", vec!["aaaaaaaaaa", "bbbbbbbbbb"], ")"),
+    ]);
+    let printed = print_with_cursor(doc, &OPTIMAL_CONFIG);
+    assert_eq!(printed.cursors[0].byte, 6);
+}
+
+#[test]
+fn layout_cost_of_text() {
+    assert_eq!(
+        get_layout_cost("[\n    aaaaaaaaaaaaaaaaaaaaa,\n\tb\n]", &GREEDY_CONFIG),
+        LayoutCost {
+            overflow: 6,
+            lines: 3,
+            nesting: 8,
+        }
+    );
+}
+
+// Long lists and deeply nested groups are laid out without running out of stack or memory.
+#[test]
+fn lays_out_large_docs() {
+    let numbers: Vec<String> = (0..2000).map(|item| item.to_string()).collect();
+    let list = items("[", numbers.iter().map(String::as_str).collect(), "]");
+    let mut nested = string("x");
+    for _ in 0..1000 {
+        nested = items_around("(", nested, ")");
+    }
+    for doc in [list, nested] {
+        let greedy = print_with_config(doc.clone(), &GREEDY_CONFIG);
+        let optimal = print_with_config(doc, &OPTIMAL_CONFIG);
+        assert!(
+            get_layout_cost(&optimal, &OPTIMAL_CONFIG) <= get_layout_cost(&greedy, &GREEDY_CONFIG)
+        );
+    }
+}
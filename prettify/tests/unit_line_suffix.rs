@@ -1,11 +1,16 @@
-use prettify::{concat, group, hard_line, line_suffix, line_suffix_boundary, print, string};
+mod fixtures;
+
+use prettify::{
+    align, break_parent, concat, group, hard_line, indent, line, line_suffix, line_suffix_boundary,
+    print, string, AlignAmount,
+};
 
 #[test]
 fn line_suffix_command() {
     assert_eq!(
         print(group(concat(vec![
             string("a"),
-            line_suffix(" // comment"),
+            line_suffix(string(" // comment")),
             string(";"),
             hard_line()
         ]))),
@@ -18,9 +23,9 @@ fn multiple_line_suffixes() {
     assert_eq!(
         print(group(concat(vec![
             string("a"),
-            line_suffix(" //"),
-            line_suffix(" "),
-            line_suffix("comment"),
+            line_suffix(string(" //")),
+            line_suffix(string(" ")),
+            line_suffix(string("comment")),
             string(";"),
             hard_line()
         ]))),
@@ -32,10 +37,10 @@ fn multiple_line_suffixes() {
 fn separated_line_suffixes() {
     assert_eq!(
         print(group(concat(vec![
-            line_suffix(" //"),
+            line_suffix(string(" //")),
             string("a"),
             string(";"),
-            line_suffix(" comment"),
+            line_suffix(string(" comment")),
             hard_line()
         ]))),
         "a; // comment\n".to_string()
@@ -44,27 +49,84 @@ fn separated_line_suffixes() {
 
 #[test]
 fn with_boundary() {
+    assert_eq!(
+        print(fixtures::line_suffix_with_boundary()),
+        "{ // comment\n}\n".to_string()
+    );
+}
+
+#[test]
+fn suffixes_always_flushed_even_without_a_newline() {
     assert_eq!(
         print(group(concat(vec![
             string("{"),
-            line_suffix(" // comment"),
+            line_suffix(string(" // comment")),
             line_suffix_boundary(),
             string("}"),
-            hard_line()
         ]))),
-        "{ // comment\n}\n".to_string()
+        "{ // comment\n}".to_string()
     );
 }
 
 #[test]
-fn suffixes_always_flushed_even_without_a_newline() {
+fn boundary_without_suffixes_prints_nothing() {
     assert_eq!(
         print(group(concat(vec![
             string("{"),
-            line_suffix(" // comment"),
             line_suffix_boundary(),
             string("}"),
         ]))),
-        "{ // comment\n}".to_string()
+        "{}".to_string()
+    );
+}
+
+#[test]
+fn suffixes_at_the_end_are_flushed() {
+    assert_eq!(
+        print(concat(vec![
+            string("a;"),
+            line_suffix(string(" // comment")),
+        ])),
+        "a; // comment".to_string()
+    );
+}
+
+#[test]
+fn suffix_with_docs() {
+    assert_eq!(
+        print(concat(vec![
+            string("a;"),
+            line_suffix(concat(vec![
+                string(" // first"),
+                indent(concat(vec![hard_line(), string("// second")])),
+            ])),
+            hard_line(),
+            string("b;"),
+        ])),
+        "a; // first\n    // second\nb;".to_string()
+    );
+    assert_eq!(
+        print(concat(vec![
+            string("a;"),
+            line_suffix(align(
+                concat(vec![string(" //"), hard_line(), string("//")]),
+                AlignAmount::Spaces(3)
+            )),
+            hard_line(),
+        ])),
+        "a; //\n   //\n".to_string()
+    );
+}
+
+#[test]
+fn suffix_with_break_parent_breaks_the_group() {
+    assert_eq!(
+        print(group(concat(vec![
+            string("a"),
+            line(),
+            string("b"),
+            line_suffix(concat(vec![string(" // comment"), break_parent()])),
+        ]))),
+        "a\nb // comment".to_string()
     );
 }
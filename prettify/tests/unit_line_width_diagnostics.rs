@@ -0,0 +1,119 @@
+use prettify::{
+    concat, group, hard_line, indent, line, literal_line, print, print_with_diagnostics, string,
+    EndOfLine, LineWidthDiagnostic, PrettifyConfig, PRETTIFY_DEFAULT_CONFIG,
+};
+
+const NARROW_CONFIG: PrettifyConfig = PrettifyConfig {
+    print_width: 10,
+    ..PRETTIFY_DEFAULT_CONFIG
+};
+
+#[test]
+fn no_diagnostics_when_everything_fits() {
+    let doc = group(concat(vec![string("a"), line(), string("b")]));
+    let printed = print_with_diagnostics(doc.clone(), &NARROW_CONFIG);
+    assert_eq!(printed.formatted, print(doc));
+    assert_eq!(printed.diagnostics, vec![]);
+}
+
+#[test]
+fn reports_the_string_that_overflows() {
+    let url = "https://example.com";
+    let doc = group(concat(vec![
+        string("see"),
+        line(),
+        string(url),
+        line(),
+        string("ok"),
+    ]));
+    let printed = print_with_diagnostics(doc, &NARROW_CONFIG);
+    assert_eq!(printed.formatted, "see\nhttps://example.com\nok");
+    assert_eq!(
+        printed.diagnostics,
+        vec![LineWidthDiagnostic {
+            line: 1,
+            width: 19,
+            string: url.to_string(),
+            output: 4..23,
+        }]
+    );
+    assert_eq!(
+        &printed.formatted[printed.diagnostics[0].output.clone()],
+        url
+    );
+}
+
+#[test]
+fn reports_the_first_string_past_the_width() {
+    let doc = concat(vec![
+        indent(concat(vec![
+            hard_line(),
+            // Adjacent strings are merged before printing, so they're kept apart by groups.
+            string("12345"),
+            group(string("678")),
+            group(string("90")),
+        ])),
+        hard_line(),
+        string("0123456789"),
+    ]);
+    let printed = print_with_diagnostics(doc, &NARROW_CONFIG);
+    assert_eq!(printed.formatted, "\n    1234567890\n0123456789");
+    assert_eq!(
+        printed.diagnostics,
+        vec![LineWidthDiagnostic {
+            line: 1,
+            width: 14,
+            string: "678".to_string(),
+            output: 10..13,
+        }]
+    );
+}
+
+#[test]
+fn trailing_whitespace_is_not_counted() {
+    let doc = concat(vec![
+        string("0123456789"),
+        string("   "),
+        hard_line(),
+        string("x"),
+        literal_line(),
+        string("01234567890"),
+    ]);
+    let printed = print_with_diagnostics(doc, &NARROW_CONFIG);
+    assert_eq!(printed.formatted, "0123456789\nx\n01234567890");
+    assert_eq!(
+        printed.diagnostics,
+        vec![LineWidthDiagnostic {
+            line: 2,
+            width: 11,
+            string: "01234567890".to_string(),
+            output: 13..24,
+        }]
+    );
+}
+
+#[test]
+fn output_range_covers_converted_line_endings() {
+    let doc = concat(vec![string("see"), hard_line(), string("0123456789ab\ncd")]);
+    let printed = print_with_diagnostics(
+        doc,
+        &PrettifyConfig {
+            end_of_line: EndOfLine::Crlf,
+            ..NARROW_CONFIG
+        },
+    );
+    assert_eq!(printed.formatted, "see\r\n0123456789ab\r\ncd");
+    assert_eq!(
+        printed.diagnostics,
+        vec![LineWidthDiagnostic {
+            line: 1,
+            width: 15,
+            string: "0123456789ab\ncd".to_string(),
+            output: 5..21,
+        }]
+    );
+    assert_eq!(
+        &printed.formatted[printed.diagnostics[0].output.clone()],
+        "0123456789ab\r\ncd"
+    );
+}
@@ -1,4 +1,9 @@
-use prettify::{concat, group, literal_line, print, string};
+mod fixtures;
+
+use prettify::{
+    concat, group, literal_line, print, print_with_config, string, LayoutEngine, PrettifyConfig,
+    PRETTIFY_DEFAULT_CONFIG,
+};
 
 #[test]
 fn command_literal_line() {
@@ -88,3 +93,21 @@ fn preserve_leading_whitespace_long_text() {
         "this is a very long piece of text that definitely overflows the line\n    this is a very long piece of text that definitely overflows the line".to_string()
     );
 }
+
+#[test]
+fn literal_line_goes_back_to_marked_root() {
+    assert_eq!(
+        print(fixtures::literal_line_in_marked_root()),
+        "a\n    b".to_string()
+    );
+    assert_eq!(
+        print_with_config(
+            fixtures::literal_line_in_marked_root(),
+            &PrettifyConfig {
+                layout_engine: LayoutEngine::Optimal,
+                ..PRETTIFY_DEFAULT_CONFIG
+            }
+        ),
+        "a\n    b".to_string()
+    );
+}
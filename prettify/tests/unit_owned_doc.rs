@@ -0,0 +1,35 @@
+use prettify::{concat, group, hard_line, line, line_suffix, print, string, PrettifyDoc};
+
+fn build_doc_from_owned_source() -> PrettifyDoc {
+    let source = String::from("first second // comment");
+    let words: Vec<&str> = source.split(' ').collect();
+    group(concat(vec![
+        string(words[0]),
+        line(),
+        string(words[1]),
+        line_suffix(string(format!(" {} {}", words[2], words[3]))),
+        hard_line(),
+    ]))
+}
+
+#[test]
+fn docs_outlive_their_source() {
+    assert_eq!(
+        print(build_doc_from_owned_source()),
+        "first\nsecond // comment\n".to_string()
+    );
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn docs_can_be_sent_between_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<PrettifyDoc>();
+
+    let doc = std::thread::spawn(build_doc_from_owned_source)
+        .join()
+        .unwrap();
+    let shared = PrettifyDoc::clone(&doc);
+    let printed = std::thread::spawn(move || print(shared)).join().unwrap();
+    assert_eq!(printed, print(doc));
+}
@@ -0,0 +1,88 @@
+use prettify::{
+    concat, group, hard_line, indent, join, line, literal_line, print, print_to_writer, string,
+    trim, PRETTIFY_DEFAULT_CONFIG,
+};
+use std::io::{self, Write};
+
+// Records every write, so the test can check when lines are streamed.
+struct RecordingWriter {
+    writes: Vec<String>,
+}
+
+impl Write for RecordingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes.push(String::from_utf8(buf.to_vec()).unwrap());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct FailingWriter;
+
+impl Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::other("closed"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn writes_the_same_output_as_print() {
+    let doc = group(concat(vec![
+        string("["),
+        indent(concat(vec![
+            line(),
+            join(
+                (0..30).map(|i| string(i.to_string())).collect(),
+                concat(vec![string(","), line()]),
+            ),
+        ])),
+        line(),
+        string("]"),
+    ]));
+    let mut result = Vec::new();
+    print_to_writer(doc.clone(), &PRETTIFY_DEFAULT_CONFIG, &mut result).unwrap();
+    assert_eq!(String::from_utf8(result).unwrap(), print(doc));
+}
+
+#[test]
+fn streams_finished_lines() {
+    let mut writer = RecordingWriter { writes: vec![] };
+    print_to_writer(
+        concat(vec![
+            string("a"),
+            hard_line(),
+            string("b"),
+            literal_line(),
+            string("c"),
+        ]),
+        &PRETTIFY_DEFAULT_CONFIG,
+        &mut writer,
+    )
+    .unwrap();
+    assert_eq!(writer.writes, vec!["a", "\nb", "\nc"]);
+}
+
+#[test]
+fn trim_after_a_line_break() {
+    let mut result = Vec::new();
+    print_to_writer(
+        indent(concat(vec![string("a"), hard_line(), trim(), string("b")])),
+        &PRETTIFY_DEFAULT_CONFIG,
+        &mut result,
+    )
+    .unwrap();
+    assert_eq!(String::from_utf8(result).unwrap(), "a\nb");
+}
+
+#[test]
+fn returns_write_errors() {
+    let result = print_to_writer(string("a"), &PRETTIFY_DEFAULT_CONFIG, &mut FailingWriter);
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Other);
+}
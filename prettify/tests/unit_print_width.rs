@@ -0,0 +1,127 @@
+use prettify::{
+    concat, conditional_group, fill, group, indent, join, join_to_vector, line, print,
+    print_with_config, soft_line, string, PrettifyConfig, PRETTIFY_DEFAULT_CONFIG,
+};
+
+fn config_with_width(print_width: usize) -> PrettifyConfig {
+    PrettifyConfig {
+        print_width,
+        ..PRETTIFY_DEFAULT_CONFIG
+    }
+}
+
+#[test]
+fn default_print_width_is_eighty() {
+    assert_eq!(PRETTIFY_DEFAULT_CONFIG.print_width, 80);
+}
+
+#[test]
+fn content_ending_at_print_width_fits() {
+    let doc = |width: usize| {
+        group(concat(vec![
+            string("a".repeat(40)),
+            line(),
+            string("b".repeat(width - 41)),
+        ]))
+    };
+    assert_eq!(
+        print(doc(80)),
+        format!("{} {}", "a".repeat(40), "b".repeat(39))
+    );
+    assert_eq!(
+        print(doc(81)),
+        format!("{}\n{}", "a".repeat(40), "b".repeat(40))
+    );
+}
+
+#[test]
+fn group_breaks_at_configured_width() {
+    let doc = || {
+        group(concat(vec![
+            string("["),
+            indent(concat(vec![
+                soft_line(),
+                join(
+                    vec![string("item1"), string("item2"), string("item3")],
+                    concat(vec![string(","), line()]),
+                ),
+            ])),
+            soft_line(),
+            string("]"),
+        ]))
+    };
+    assert_eq!(print(doc()), "[item1, item2, item3]".to_string());
+    assert_eq!(
+        print_with_config(doc(), &config_with_width(20)),
+        "[\n    item1,\n    item2,\n    item3\n]".to_string()
+    );
+}
+
+#[test]
+fn wide_print_width_keeps_long_groups_flat() {
+    let mut items = Vec::new();
+    for i in 0..20 {
+        items.push(string(format!("item{}", i)));
+    }
+    let doc = group(concat(vec![
+        string("["),
+        indent(concat(vec![
+            soft_line(),
+            join(items, concat(vec![string(","), line()])),
+        ])),
+        soft_line(),
+        string("]"),
+    ]));
+    assert_eq!(
+        print_with_config(doc, &config_with_width(200)),
+        "[item0, item1, item2, item3, item4, item5, item6, item7, item8, item9, item10, item11, item12, item13, item14, item15, item16, item17, item18, item19]".to_string()
+    );
+}
+
+#[test]
+fn fill_wraps_at_configured_width() {
+    let mut items = Vec::new();
+    for i in 0..10 {
+        items.push(string(format!("item {},", i)));
+    }
+    assert_eq!(
+        print_with_config(fill(join_to_vector(items, line())), &config_with_width(31)),
+        "item 0, item 1, item 2, item 3,\nitem 4, item 5, item 6, item 7,\nitem 8, item 9,"
+            .to_string()
+    );
+}
+
+#[test]
+fn conditional_group_uses_configured_width() {
+    let doc = || {
+        conditional_group(
+            vec![
+                string("this state is forty characters long....."),
+                string("short state"),
+            ],
+            None,
+        )
+    };
+    assert_eq!(
+        print(doc()),
+        "this state is forty characters long.....".to_string()
+    );
+    assert_eq!(
+        print_with_config(doc(), &config_with_width(20)),
+        "short state".to_string()
+    );
+}
+
+#[test]
+fn content_wider_than_print_width_does_not_panic() {
+    assert_eq!(
+        print_with_config(
+            concat(vec![
+                string("this is longer than the print width"),
+                group(concat(vec![line(), string("end")])),
+            ]),
+            &config_with_width(10)
+        ),
+        "this is longer than the print width\nend".to_string()
+    );
+}
@@ -0,0 +1,70 @@
+use prettify::{
+    concat, conditional_group, group, hard_line, hard_line_without_break_parent, indent, line,
+    print, propagate_breaks, string, PrettifyDoc,
+};
+
+#[test]
+fn hard_line_breaks_ancestor_groups() {
+    assert_eq!(
+        print(group(concat(vec![
+            string("a"),
+            line(),
+            group(concat(vec![
+                string("b"),
+                line(),
+                indent(group(concat(vec![string("c"), hard_line(), string("d")]))),
+            ])),
+        ]))),
+        "a\nb\nc\n    d".to_string()
+    );
+}
+
+#[test]
+fn hard_line_without_break_parent_does_not_propagate() {
+    assert_eq!(
+        print(group(concat(vec![
+            string("a"),
+            line(),
+            group(concat(vec![
+                string("b"),
+                hard_line_without_break_parent(),
+                string("c")
+            ])),
+        ]))),
+        "a b\nc".to_string()
+    );
+}
+
+#[test]
+fn breaks_groups_inside_conditional_group_states() {
+    let state = group(concat(vec![string("a"), line(), string("b"), hard_line()]));
+    assert_eq!(
+        print(conditional_group(
+            vec![PrettifyDoc::clone(&state), state],
+            None,
+        )),
+        "a\nb\n".to_string()
+    );
+}
+
+#[test]
+fn docs_without_breaks_are_unchanged() {
+    let doc = group(concat(vec![string("a"), line(), string("b")]));
+    assert!(PrettifyDoc::ptr_eq(
+        &propagate_breaks(PrettifyDoc::clone(&doc)),
+        &doc
+    ));
+}
+
+#[test]
+fn shared_subtrees_stay_shared() {
+    let shared: PrettifyDoc = group(concat(vec![string("a"), hard_line(), string("b")]));
+    let doc = concat(vec![PrettifyDoc::clone(&shared), line(), shared]);
+    let propagated = propagate_breaks(doc);
+    assert_eq!(
+        print(PrettifyDoc::clone(&propagated)),
+        "a\nb\na\nb".to_string()
+    );
+    let propagated_again = propagate_breaks(PrettifyDoc::clone(&propagated));
+    assert!(PrettifyDoc::ptr_eq(&propagated, &propagated_again));
+}
@@ -0,0 +1,71 @@
+use prettify::{
+    concat, group, hard_line, indent, line, print, print_with_source_map, source_span, string,
+    trim, SourceMapping, PRETTIFY_DEFAULT_CONFIG,
+};
+
+#[test]
+fn maps_output_to_source() {
+    let doc = group(concat(vec![
+        string("["),
+        indent(concat(vec![
+            hard_line(),
+            source_span(string("1"), 1..2),
+            string(","),
+            line(),
+            source_span(string("22"), 4..6),
+        ])),
+        hard_line(),
+        string("]"),
+    ]));
+    let printed = print_with_source_map(doc.clone(), &PRETTIFY_DEFAULT_CONFIG);
+    assert_eq!(printed.formatted, print(doc));
+    assert_eq!(printed.formatted, "[\n    1,\n    22\n]");
+    assert_eq!(
+        printed.mappings,
+        vec![
+            SourceMapping {
+                output: 6..7,
+                input: 1..2,
+            },
+            SourceMapping {
+                output: 13..15,
+                input: 4..6,
+            },
+        ]
+    );
+}
+
+#[test]
+fn nested_spans() {
+    let doc = source_span(
+        concat(vec![string("a "), source_span(string("b"), 2..3)]),
+        0..3,
+    );
+    assert_eq!(
+        print_with_source_map(doc, &PRETTIFY_DEFAULT_CONFIG).mappings,
+        vec![
+            SourceMapping {
+                output: 0..3,
+                input: 0..3,
+            },
+            SourceMapping {
+                output: 2..3,
+                input: 2..3,
+            },
+        ]
+    );
+}
+
+#[test]
+fn spans_do_not_extend_past_the_output() {
+    let doc = concat(vec![source_span(string("a  "), 0..3), trim()]);
+    let printed = print_with_source_map(doc, &PRETTIFY_DEFAULT_CONFIG);
+    assert_eq!(printed.formatted, "a");
+    assert_eq!(
+        printed.mappings,
+        vec![SourceMapping {
+            output: 0..1,
+            input: 0..3,
+        }]
+    );
+}
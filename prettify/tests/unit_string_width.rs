@@ -0,0 +1,88 @@
+use prettify::{
+    concat, fill, group, indent, join, join_to_vector, line, print, print_with_config, soft_line,
+    string, PrettifyConfig, PrettifyDoc, PRETTIFY_DEFAULT_CONFIG,
+};
+
+fn bracketed(items: Vec<&str>) -> PrettifyDoc {
+    group(concat(vec![
+        string("["),
+        indent(concat(vec![
+            soft_line(),
+            join(
+                items.into_iter().map(string).collect(),
+                concat(vec![string(","), line()]),
+            ),
+        ])),
+        soft_line(),
+        string("]"),
+    ]))
+}
+
+#[test]
+fn accents_count_as_one_column() {
+    // 40 characters, but 60 bytes
+    let word = "é".repeat(20);
+    assert_eq!(
+        print(bracketed(vec![&word, &word])),
+        format!("[{}, {}]", word, word)
+    );
+}
+
+#[test]
+fn combining_marks_count_as_one_column() {
+    let word = "e\u{301}".repeat(20);
+    assert_eq!(
+        print(bracketed(vec![&word, &word])),
+        format!("[{}, {}]", word, word)
+    );
+}
+
+#[test]
+fn wide_characters_count_as_two_columns() {
+    // 9 characters and 27 bytes, but 18 columns wide
+    let word = "日本語".repeat(3);
+    let narrow = PrettifyConfig {
+        print_width: 30,
+        ..PRETTIFY_DEFAULT_CONFIG
+    };
+    assert_eq!(
+        print_with_config(bracketed(vec![&word, &word]), &narrow),
+        format!("[\n    {},\n    {}\n]", word, word)
+    );
+    let wide = PrettifyConfig {
+        print_width: 41,
+        ..PRETTIFY_DEFAULT_CONFIG
+    };
+    assert_eq!(
+        print_with_config(bracketed(vec![&word, &word]), &wide),
+        format!("[{}, {}]", word, word)
+    );
+}
+
+#[test]
+fn emoji_sequences_count_as_two_columns() {
+    let family = "👨\u{200d}👩\u{200d}👧";
+    let doc = bracketed(vec![family; 10]);
+    let config = PrettifyConfig {
+        print_width: 41,
+        ..PRETTIFY_DEFAULT_CONFIG
+    };
+    assert_eq!(
+        print_with_config(doc, &config),
+        format!("[{}]", [family; 10].join(", "))
+    );
+}
+
+#[test]
+fn fill_wraps_at_visual_width() {
+    let words: Vec<_> = (0..12).map(|_| string("日本語")).collect();
+    let config = PrettifyConfig {
+        print_width: 27,
+        ..PRETTIFY_DEFAULT_CONFIG
+    };
+    assert_eq!(
+        print_with_config(fill(join_to_vector(words, line())), &config),
+        "日本語 日本語 日本語 日本語\n日本語 日本語 日本語 日本語\n日本語 日本語 日本語 日本語"
+            .to_string()
+    );
+}
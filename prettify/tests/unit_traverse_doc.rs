@@ -0,0 +1,154 @@
+use prettify::{
+    concat, conditional_group, cursor, fill, group, hard_line, indent, line, map_doc, print,
+    print_doc_to_debug, string, traverse_doc, Doc, DocCommand, LineMode, PrettifyDoc,
+};
+
+fn is_string(doc: &PrettifyDoc, text: &str) -> bool {
+    matches!(doc.as_ref(), Doc::String(string) if string == text)
+}
+
+#[test]
+fn traverse_doc_enters_and_exits_in_order() {
+    let doc = group(concat(vec![string("a"), indent(string("b"))]));
+    let mut events = Vec::new();
+    let events_cell = std::cell::RefCell::new(&mut events);
+    traverse_doc(
+        &doc,
+        |doc| {
+            events_cell
+                .borrow_mut()
+                .push(format!("enter {}", print(PrettifyDoc::clone(doc))));
+            true
+        },
+        |doc| {
+            events_cell
+                .borrow_mut()
+                .push(format!("exit {}", print(PrettifyDoc::clone(doc))));
+        },
+    );
+    assert_eq!(
+        events,
+        vec![
+            "enter ab", "enter ab", "enter a", "exit a", "enter b", "enter b", "exit b", "exit b",
+            "exit ab", "exit ab",
+        ]
+    );
+}
+
+#[test]
+fn traverse_doc_finds_cursors_in_fill_and_conditional_group() {
+    let doc = concat(vec![
+        fill(vec![string("a"), line(), cursor()]),
+        conditional_group(vec![string("b"), concat(vec![cursor(), string("c")])], None),
+    ]);
+    let mut cursors = 0;
+    traverse_doc(
+        &doc,
+        |doc| {
+            if matches!(doc.as_ref(), Doc::Command(DocCommand::Cursor)) {
+                cursors += 1;
+            }
+            true
+        },
+        |_| {},
+    );
+    assert_eq!(cursors, 2);
+}
+
+#[test]
+fn traverse_doc_skips_children() {
+    let doc = concat(vec![group(string("a")), string("b")]);
+    let mut visited = 0;
+    traverse_doc(
+        &doc,
+        |doc| {
+            visited += 1;
+            !matches!(doc.as_ref(), Doc::Command(_))
+        },
+        |_| {},
+    );
+    assert_eq!(visited, 3);
+}
+
+#[test]
+fn map_doc_replaces_subtrees() {
+    let doc = group(concat(vec![string("a"), line(), string("b")]));
+    let mapped = map_doc(&doc, |doc| {
+        if is_string(doc, "b") {
+            string("c")
+        } else {
+            PrettifyDoc::clone(doc)
+        }
+    });
+    assert_eq!(print(mapped), "a c");
+    assert_eq!(print(doc), "a b");
+}
+
+// `hard_line()` is a concat of a hard line and a break parent.
+fn is_hard_line(doc: &PrettifyDoc) -> bool {
+    match doc.as_ref() {
+        Doc::Children(children) => matches!(
+            children[0].as_ref(),
+            Doc::Command(DocCommand::Line(LineMode::Hard))
+        ),
+        _ => false,
+    }
+}
+
+#[test]
+fn map_doc_strips_trailing_hard_lines() {
+    let doc = concat(vec![string("a"), hard_line(), hard_line()]);
+    let mapped = map_doc(&doc, |doc| match doc.as_ref() {
+        Doc::Children(children) => {
+            let mut children = children.clone();
+            while children.last().is_some_and(is_hard_line) {
+                children.pop();
+            }
+            concat(children)
+        }
+        _ => PrettifyDoc::clone(doc),
+    });
+    assert_eq!(print(mapped), "a");
+}
+
+#[test]
+fn map_doc_preserves_sharing() {
+    let shared = group(concat(vec![string("a"), line(), string("b")]));
+    let unchanged = string("c");
+    let doc = concat(vec![
+        PrettifyDoc::clone(&shared),
+        PrettifyDoc::clone(&shared),
+        PrettifyDoc::clone(&unchanged),
+    ]);
+    let mapped = map_doc(&doc, |doc| {
+        if is_string(doc, "a") {
+            string("A")
+        } else {
+            PrettifyDoc::clone(doc)
+        }
+    });
+    match mapped.as_ref() {
+        Doc::Children(children) => {
+            assert!(PrettifyDoc::ptr_eq(&children[0], &children[1]));
+            assert!(!PrettifyDoc::ptr_eq(&children[0], &shared));
+            assert!(PrettifyDoc::ptr_eq(&children[2], &unchanged));
+        }
+        _ => panic!("expected a concat"),
+    }
+}
+
+#[test]
+fn map_doc_maps_conditional_group_states() {
+    let doc = conditional_group(vec![string("a"), string("b")], None);
+    let mapped = map_doc(&doc, |doc| {
+        if is_string(doc, "b") {
+            string("c")
+        } else {
+            PrettifyDoc::clone(doc)
+        }
+    });
+    assert_eq!(
+        print_doc_to_debug(mapped),
+        r#"conditional_group(vec![string("a"), string("c")], None)"#
+    );
+}
@@ -7,3 +7,16 @@ fn trim_command() {
         "    hello".to_string()
     );
 }
+
+#[test]
+fn trim_whitespace_across_strings() {
+    assert_eq!(
+        print(concat(vec![
+            string("hello \t"),
+            string("  "),
+            trim(),
+            string(" world")
+        ])),
+        "hello world".to_string()
+    );
+}
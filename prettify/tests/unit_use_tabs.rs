@@ -0,0 +1,158 @@
+use indoc::indoc;
+use prettify::{
+    align, concat, group, hard_line, indent, indent_by, join, line, print_with_config, soft_line,
+    string, trim, AlignAmount, PrettifyConfig, PRETTIFY_DEFAULT_CONFIG,
+};
+
+const TABS_CONFIG: PrettifyConfig = PrettifyConfig {
+    use_tabs: true,
+    ..PRETTIFY_DEFAULT_CONFIG
+};
+
+#[test]
+fn indent_uses_tabs() {
+    assert_eq!(
+        print_with_config(
+            group(indent(concat(vec![
+                hard_line(),
+                string("one"),
+                indent(concat(vec![hard_line(), string("two")])),
+            ]))),
+            &TABS_CONFIG
+        ),
+        "\n\tone\n\t\ttwo".to_string()
+    );
+}
+
+#[test]
+fn trailing_align_stays_spaces() {
+    assert_eq!(
+        print_with_config(
+            group(indent(align(
+                concat(vec![hard_line(), string("aligned")]),
+                AlignAmount::Spaces(2)
+            ))),
+            &TABS_CONFIG
+        ),
+        "\n\t  aligned".to_string()
+    );
+}
+
+#[test]
+fn align_followed_by_indent_becomes_a_tab() {
+    assert_eq!(
+        print_with_config(
+            group(align(
+                indent(concat(vec![hard_line(), string("aligned")])),
+                AlignAmount::Spaces(2)
+            )),
+            &TABS_CONFIG
+        ),
+        "\n\t\taligned".to_string()
+    );
+}
+
+#[test]
+fn string_align_is_kept_verbatim() {
+    assert_eq!(
+        print_with_config(
+            group(indent(align(
+                concat(vec![hard_line(), string("quoted")]),
+                AlignAmount::String("> ".to_string())
+            ))),
+            &TABS_CONFIG
+        ),
+        "\n\t> quoted".to_string()
+    );
+}
+
+#[test]
+fn tabs_count_as_tab_width_when_measuring() {
+    let doc = || {
+        indent(indent(concat(vec![
+            hard_line(),
+            group(concat(vec![
+                string("["),
+                indent(concat(vec![
+                    soft_line(),
+                    join(
+                        vec![string("item1"), string("item2")],
+                        concat(vec![string(","), line()]),
+                    ),
+                ])),
+                soft_line(),
+                string("]"),
+            ])),
+        ])))
+    };
+    // Two tabs are 8 columns wide, so the group only fits in 23 columns, even though
+    // the line is only 16 bytes long.
+    let narrow = PrettifyConfig {
+        print_width: 20,
+        ..TABS_CONFIG
+    };
+    assert_eq!(
+        print_with_config(doc(), &narrow),
+        indoc! {"
+
+            \t\t[
+            \t\t\titem1,
+            \t\t\titem2
+            \t\t]"}
+    );
+    let wide_enough = PrettifyConfig {
+        print_width: 23,
+        ..TABS_CONFIG
+    };
+    assert_eq!(
+        print_with_config(doc(), &wide_enough),
+        "\n\t\t[item1, item2]".to_string()
+    );
+}
+
+#[test]
+fn trimming_tabs_restores_the_column() {
+    let narrow = PrettifyConfig {
+        print_width: 14,
+        ..TABS_CONFIG
+    };
+    assert_eq!(
+        print_with_config(
+            indent(concat(vec![
+                hard_line(),
+                trim(),
+                group(concat(vec![
+                    string("one"),
+                    line(),
+                    string("two"),
+                    line(),
+                    string("three")
+                ])),
+            ])),
+            &narrow
+        ),
+        "\none two three".to_string()
+    );
+}
+
+#[test]
+fn trailing_indent_by_stays_spaces() {
+    assert_eq!(
+        print_with_config(
+            indent_by(2, concat(vec![hard_line(), string("indented")])),
+            &TABS_CONFIG
+        ),
+        "\n  indented".to_string()
+    );
+}
+
+#[test]
+fn indent_by_followed_by_indent_uses_tabs() {
+    assert_eq!(
+        print_with_config(
+            indent_by(2, indent(concat(vec![hard_line(), string("indented")]))),
+            &TABS_CONFIG
+        ),
+        "\n\t\tindented".to_string()
+    );
+}
@@ -0,0 +1,146 @@
+use prettify::{
+    concat, conditional_group, dedent, dedent_to_mark, dedent_to_root, fill, group,
+    group_with_options, hard_line, if_break, indent, indent_if_break, line, mark_as_root, string,
+    validate_doc, DocDiagnostic, DocDiagnosticKind, DocOptions, GroupIdAllocator,
+};
+
+#[test]
+fn valid_doc() {
+    let group_ids = GroupIdAllocator::new();
+    let id = group_ids.allocate();
+    let doc = concat(vec![
+        group_with_options(
+            indent(concat(vec![string("a"), line(), dedent(string("b"))])),
+            DocOptions {
+                id: Some(id),
+                ..DocOptions::default()
+            },
+        ),
+        if_break(string(","), string(""), Some(id)),
+        fill(vec![
+            string("a"),
+            hard_line(),
+            group(concat(vec![string("b"), hard_line()])),
+        ]),
+    ]);
+    assert_eq!(validate_doc(&doc), vec![]);
+}
+
+#[test]
+fn fill_with_even_parts() {
+    let doc = concat(vec![string("a"), fill(vec![string("b"), line()])]);
+    assert_eq!(
+        validate_doc(&doc),
+        vec![DocDiagnostic {
+            kind: DocDiagnosticKind::FillWithEvenParts(2),
+            path: vec![1],
+        }]
+    );
+}
+
+#[test]
+fn unknown_group_id() {
+    let group_ids = GroupIdAllocator::new();
+    let id = group_ids.allocate();
+    let doc = group(concat(vec![
+        if_break(string(","), string(""), Some(id)),
+        indent_if_break(string("a"), id, false),
+    ]));
+    assert_eq!(
+        validate_doc(&doc),
+        vec![
+            DocDiagnostic {
+                kind: DocDiagnosticKind::UnknownGroupId(id),
+                path: vec![0, 0],
+            },
+            DocDiagnostic {
+                kind: DocDiagnosticKind::UnknownGroupId(id),
+                path: vec![0, 1],
+            },
+        ]
+    );
+}
+
+#[test]
+fn dedent_to_mark_on_root() {
+    let doc = indent(mark_as_root(indent(concat(vec![
+        dedent_to_mark(dedent(string("a"))),
+        dedent_to_mark(dedent(dedent(string("b")))),
+    ]))));
+    assert_eq!(
+        validate_doc(&doc),
+        vec![DocDiagnostic {
+            kind: DocDiagnosticKind::DedentOnRoot,
+            path: vec![0, 0, 0, 1, 0, 0],
+        }]
+    );
+}
+
+#[test]
+fn dedent_on_root() {
+    let doc = indent(concat(vec![
+        dedent(string("a")),
+        dedent(dedent(string("b"))),
+    ]));
+    assert_eq!(
+        validate_doc(&doc),
+        vec![DocDiagnostic {
+            kind: DocDiagnosticKind::DedentOnRoot,
+            path: vec![0, 1, 0],
+        }]
+    );
+}
+
+#[test]
+fn dedent_to_root_and_mark_on_root_are_valid() {
+    let doc = concat(vec![
+        dedent_to_root(string("a")),
+        dedent_to_mark(string("b")),
+    ]);
+    assert_eq!(validate_doc(&doc), vec![]);
+}
+
+// Each state shares the doc of the level below, so it would be checked 2^40 times if shared
+// docs weren't only checked once.
+#[test]
+fn nested_conditional_groups() {
+    let mut doc = string("a");
+    for _ in 0..40 {
+        doc = conditional_group(
+            vec![
+                doc.clone(),
+                concat(vec![doc.clone(), hard_line(), doc.clone()]),
+            ],
+            None,
+        );
+    }
+    assert_eq!(validate_doc(&doc), vec![]);
+}
+
+#[test]
+fn hard_line_in_flat_fill() {
+    let doc = fill(vec![
+        concat(vec![string("a"), hard_line()]),
+        line(),
+        string("b"),
+    ]);
+    assert_eq!(
+        validate_doc(&doc),
+        vec![DocDiagnostic {
+            kind: DocDiagnosticKind::HardLineInFlatFill,
+            path: vec![0, 1, 0],
+        }]
+    );
+}
+
+#[test]
+fn diagnostic_display() {
+    assert_eq!(
+        DocDiagnostic {
+            kind: DocDiagnosticKind::FillWithEvenParts(2),
+            path: vec![1],
+        }
+        .to_string(),
+        "fill has 2 parts, but it needs an odd number of parts at [1]"
+    );
+}